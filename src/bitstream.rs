@@ -0,0 +1,143 @@
+use std::io;
+
+/// MSB-first bit packer shared by [`crate::delta_encoding::DeltaEncoding`]
+/// and [`crate::huffman::Huffman`], in the spirit of the bit writer/reader
+/// pair classic inflate implementations use to pack variable-width codes
+/// without wasting bits to byte alignment.
+pub struct BitWriter {
+    bytes: Vec<u8>,
+    cur: u8,
+    nbits: u8,
+}
+
+impl BitWriter {
+    pub fn new() -> Self {
+        BitWriter {
+            bytes: Vec::new(),
+            cur: 0,
+            nbits: 0,
+        }
+    }
+
+    /// Writes the low `len` bits of `value`, most-significant bit first.
+    pub fn write_bits(&mut self, value: u32, len: u8) {
+        for i in (0..len).rev() {
+            let bit = ((value >> i) & 1) as u8;
+            self.cur = (self.cur << 1) | bit;
+            self.nbits += 1;
+            if self.nbits == 8 {
+                self.bytes.push(self.cur);
+                self.cur = 0;
+                self.nbits = 0;
+            }
+        }
+    }
+
+    /// Writes the low `len` bits of `value` (len <= 64), most-significant
+    /// bit first.
+    pub fn write_bits_u64(&mut self, value: u64, len: u8) {
+        if len > 32 {
+            self.write_bits((value >> 32) as u32, len - 32);
+            self.write_bits(value as u32, 32);
+        } else {
+            self.write_bits(value as u32, len);
+        }
+    }
+
+    /// Flushes any residual bits, zero-padding the final byte, and returns
+    /// the packed buffer.
+    pub fn finish(mut self) -> Vec<u8> {
+        if self.nbits > 0 {
+            self.cur <<= 8 - self.nbits;
+            self.bytes.push(self.cur);
+        }
+        self.bytes
+    }
+}
+
+impl Default for BitWriter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub struct BitReader<'a> {
+    data: &'a [u8],
+    byte_pos: usize,
+    bit_pos: u8,
+}
+
+impl<'a> BitReader<'a> {
+    pub fn new(data: &'a [u8]) -> Self {
+        BitReader {
+            data,
+            byte_pos: 0,
+            bit_pos: 0,
+        }
+    }
+
+    pub fn read_bit(&mut self) -> io::Result<u8> {
+        if self.byte_pos >= self.data.len() {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "Bit stream underrun",
+            ));
+        }
+        let bit = (self.data[self.byte_pos] >> (7 - self.bit_pos)) & 1;
+        self.bit_pos += 1;
+        if self.bit_pos == 8 {
+            self.bit_pos = 0;
+            self.byte_pos += 1;
+        }
+        Ok(bit)
+    }
+
+    /// Reads `len` bits (len <= 32), most-significant bit first.
+    pub fn read_bits(&mut self, len: u8) -> io::Result<u32> {
+        let mut value: u32 = 0;
+        for _ in 0..len {
+            value = (value << 1) | self.read_bit()? as u32;
+        }
+        Ok(value)
+    }
+
+    /// Reads `len` bits (len <= 64), most-significant bit first.
+    pub fn read_bits_u64(&mut self, len: u8) -> io::Result<u64> {
+        if len > 32 {
+            let high = self.read_bits(len - 32)? as u64;
+            let low = self.read_bits(32)? as u64;
+            Ok((high << 32) | low)
+        } else {
+            Ok(self.read_bits(len)? as u64)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bitwriter_reader_roundtrip() {
+        let mut writer = BitWriter::new();
+        writer.write_bits(0b00, 2);
+        writer.write_bits(0b1010, 4);
+        writer.write_bits(0b01, 2);
+        writer.write_bits(0x1FFF, 14);
+
+        let bytes = writer.finish();
+        let mut reader = BitReader::new(&bytes);
+
+        assert_eq!(reader.read_bits(2).unwrap(), 0b00);
+        assert_eq!(reader.read_bits(4).unwrap(), 0b1010);
+        assert_eq!(reader.read_bits(2).unwrap(), 0b01);
+        assert_eq!(reader.read_bits(14).unwrap(), 0x1FFF);
+    }
+
+    #[test]
+    fn test_bitreader_underrun() {
+        let bytes = [0u8];
+        let mut reader = BitReader::new(&bytes);
+        assert!(reader.read_bits(16).is_err());
+    }
+}
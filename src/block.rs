@@ -0,0 +1,360 @@
+use std::io::{self, Read, Seek, SeekFrom, Write};
+
+use crate::codec::{codec_for_id, Codec, Store};
+use crate::compression::{CompressedTimeSeries, CompressedTimeSeriesBuilder};
+use crate::crc32::Crc32;
+use crate::types::Tick;
+
+/// Ticks buffered per block before [`BlockWriter`] seals one automatically.
+/// Small enough that a crash mid-capture loses at most a few seconds of a
+/// typical tick feed.
+const DEFAULT_BLOCK_TICKS: usize = 4096;
+
+/// Four-byte tag at the very end of a block-structured file, so a reader
+/// can tell a block index is present (and not, say, a bare single-block
+/// [`CompressedTimeSeries::write_to`] stream) before trusting the footer.
+const FOOTER_MAGIC: &[u8; 4] = b"HBIX";
+
+/// `count(u32) + index_crc(u32) + magic(4)`.
+const FOOTER_LEN: usize = 4 + 4 + 4;
+
+/// One sealed block's position in the file plus the timestamp range it
+/// covers. [`BlockWriter::finish`] appends these as a small index at the
+/// tail of the file so [`BlockReader`] can seek straight to the block
+/// covering a requested timestamp instead of scanning every block.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BlockIndexEntry {
+    pub offset: u64,
+    pub length: u64,
+    pub first_ts: u64,
+    pub last_ts: u64,
+}
+
+impl BlockIndexEntry {
+    const ENCODED_LEN: usize = 32;
+
+    fn write_to(&self, buf: &mut Vec<u8>) {
+        buf.extend_from_slice(&self.offset.to_le_bytes());
+        buf.extend_from_slice(&self.length.to_le_bytes());
+        buf.extend_from_slice(&self.first_ts.to_le_bytes());
+        buf.extend_from_slice(&self.last_ts.to_le_bytes());
+    }
+
+    fn read_from(data: &[u8]) -> Self {
+        BlockIndexEntry {
+            offset: u64::from_le_bytes(data[0..8].try_into().unwrap()),
+            length: u64::from_le_bytes(data[8..16].try_into().unwrap()),
+            first_ts: u64::from_le_bytes(data[16..24].try_into().unwrap()),
+            last_ts: u64::from_le_bytes(data[24..32].try_into().unwrap()),
+        }
+    }
+}
+
+/// Incremental writer for the block-structured `.hdrs` format: ticks are
+/// appended via [`Self::push`] and flushed into independent, self-contained
+/// blocks (each just a [`CompressedTimeSeries::write_to`] stream, with its
+/// own reference frame and CRCs) every `flush_every` ticks, so a crash
+/// mid-capture loses at most one partial block. [`Self::finish`] seals any
+/// remainder and appends the block index.
+///
+/// A file produced from fewer than `flush_every` pushed ticks followed by
+/// `finish` is the existing single-block format plus one trailing index
+/// entry and the footer — the block format is a strict superset of the
+/// original.
+pub struct BlockWriter<W: Write> {
+    inner: W,
+    codec_id: u8,
+    lossless: bool,
+    flush_every: usize,
+    pending: Vec<Tick>,
+    offset: u64,
+    index: Vec<BlockIndexEntry>,
+}
+
+impl<W: Write> BlockWriter<W> {
+    pub fn new(inner: W) -> Self {
+        Self::with_flush_every(inner, DEFAULT_BLOCK_TICKS)
+    }
+
+    /// Like [`Self::new`], but seals a block every `flush_every` pushed
+    /// ticks instead of the default.
+    pub fn with_flush_every(inner: W, flush_every: usize) -> Self {
+        BlockWriter {
+            inner,
+            codec_id: Store.id(),
+            lossless: false,
+            flush_every,
+            pending: Vec::new(),
+            offset: 0,
+            index: Vec::new(),
+        }
+    }
+
+    pub fn codec(mut self, codec: Box<dyn Codec>) -> Self {
+        self.codec_id = codec.id();
+        self
+    }
+
+    /// Opts into exact `f64` round-trips via Gorilla XOR encoding for every
+    /// block, mirroring [`CompressedTimeSeriesBuilder::lossless`].
+    pub fn lossless(mut self, lossless: bool) -> Self {
+        self.lossless = lossless;
+        self
+    }
+
+    /// Buffers `tick`, sealing and flushing a block once `flush_every`
+    /// ticks have accumulated.
+    pub fn push(&mut self, tick: Tick) -> io::Result<()> {
+        self.pending.push(tick);
+        if self.pending.len() >= self.flush_every {
+            self.seal_block()?;
+        }
+        Ok(())
+    }
+
+    fn seal_block(&mut self) -> io::Result<()> {
+        if self.pending.is_empty() {
+            return Ok(());
+        }
+
+        let ticks = std::mem::take(&mut self.pending);
+        let first_ts = ticks[0].timestamp;
+        let last_ts = ticks[ticks.len() - 1].timestamp;
+
+        let codec = codec_for_id(self.codec_id)?;
+        let series = CompressedTimeSeriesBuilder::new()
+            .codec(codec)
+            .lossless(self.lossless)
+            .compress(&ticks)?;
+
+        let written = series.write_to(&mut self.inner)? as u64;
+        self.index.push(BlockIndexEntry {
+            offset: self.offset,
+            length: written,
+            first_ts,
+            last_ts,
+        });
+        self.offset += written;
+        Ok(())
+    }
+
+    /// Seals any buffered remainder, writes the block index, and returns
+    /// the underlying writer.
+    pub fn finish(mut self) -> io::Result<W> {
+        self.seal_block()?;
+
+        let crc = Crc32::new();
+        let mut index_bytes = Vec::with_capacity(self.index.len() * BlockIndexEntry::ENCODED_LEN);
+        for entry in &self.index {
+            entry.write_to(&mut index_bytes);
+        }
+        let index_crc = crc.checksum(&index_bytes);
+
+        self.inner.write_all(&index_bytes)?;
+        self.inner
+            .write_all(&(self.index.len() as u32).to_le_bytes())?;
+        self.inner.write_all(&index_crc.to_le_bytes())?;
+        self.inner.write_all(FOOTER_MAGIC)?;
+
+        Ok(self.inner)
+    }
+}
+
+/// Random-access reader over the block-structured format written by
+/// [`BlockWriter`]. Parses the trailing block index once in [`Self::open`],
+/// then decompresses one block at a time via [`Self::read_block_into`] so
+/// callers never need to hold every tick in the file in memory at once.
+pub struct BlockReader<R> {
+    inner: R,
+    index: Vec<BlockIndexEntry>,
+    file_len: u64,
+}
+
+impl<R: Read + Seek> BlockReader<R> {
+    pub fn open(mut inner: R) -> io::Result<Self> {
+        let end = inner.seek(SeekFrom::End(0))?;
+        if end < FOOTER_LEN as u64 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "Truncated block index footer",
+            ));
+        }
+
+        inner.seek(SeekFrom::End(-(FOOTER_LEN as i64)))?;
+        let mut footer = [0u8; FOOTER_LEN];
+        inner.read_exact(&mut footer)?;
+
+        let count = u32::from_le_bytes(footer[0..4].try_into().unwrap()) as usize;
+        let index_crc = u32::from_le_bytes(footer[4..8].try_into().unwrap());
+        if &footer[8..12] != FOOTER_MAGIC {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "Missing block index magic",
+            ));
+        }
+
+        let index_len = count * BlockIndexEntry::ENCODED_LEN;
+        let index_start = end
+            .checked_sub(FOOTER_LEN as u64)
+            .and_then(|p| p.checked_sub(index_len as u64))
+            .ok_or_else(|| {
+                io::Error::new(io::ErrorKind::InvalidData, "Block index longer than file")
+            })?;
+
+        inner.seek(SeekFrom::Start(index_start))?;
+        let mut index_bytes = vec![0u8; index_len];
+        inner.read_exact(&mut index_bytes)?;
+
+        let crc = Crc32::new();
+        if crc.checksum(&index_bytes) != index_crc {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "Block index checksum mismatch",
+            ));
+        }
+
+        let index = index_bytes
+            .chunks_exact(BlockIndexEntry::ENCODED_LEN)
+            .map(BlockIndexEntry::read_from)
+            .collect();
+
+        Ok(BlockReader { inner, index, file_len: end })
+    }
+
+    /// The parsed block index, in file order.
+    pub fn index(&self) -> &[BlockIndexEntry] {
+        &self.index
+    }
+
+    pub fn num_blocks(&self) -> usize {
+        self.index.len()
+    }
+
+    /// Decompresses block `i` into `out`, clearing it first. Lets callers
+    /// reuse one `Vec<Tick>` allocation across many blocks instead of
+    /// allocating a fresh one each time.
+    pub fn read_block_into(&mut self, i: usize, out: &mut Vec<Tick>) -> io::Result<()> {
+        let entry = *self.index.get(i).ok_or_else(|| {
+            io::Error::new(io::ErrorKind::InvalidInput, "Block index out of range")
+        })?;
+
+        if entry.length > self.file_len.saturating_sub(entry.offset) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "Block length longer than file",
+            ));
+        }
+
+        self.inner.seek(SeekFrom::Start(entry.offset))?;
+        let mut buf = vec![0u8; entry.length as usize];
+        self.inner.read_exact(&mut buf)?;
+
+        let series = CompressedTimeSeries::deserialize(&buf)?;
+        out.clear();
+        out.extend(series.decompress()?);
+        Ok(())
+    }
+
+    pub fn read_block(&mut self, i: usize) -> io::Result<Vec<Tick>> {
+        let mut out = Vec::new();
+        self.read_block_into(i, &mut out)?;
+        Ok(out)
+    }
+
+    /// Finds the first block whose timestamp range can cover `ts` via the
+    /// index and decompresses just that block, instead of scanning every
+    /// preceding one. Returns `None` if `ts` is past the last block.
+    pub fn seek_time(&mut self, ts: u64) -> io::Result<Option<Vec<Tick>>> {
+        let pos = self.index.partition_point(|e| e.last_ts < ts);
+        if pos >= self.index.len() {
+            return Ok(None);
+        }
+        Ok(Some(self.read_block(pos)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    fn make_tick(ts: u64, aapl: f64, googl: f64) -> Tick {
+        Tick {
+            timestamp: ts,
+            prices: [("AAPL", aapl), ("GOOGL", googl)]
+                .iter()
+                .map(|(k, v)| (k.to_string(), *v))
+                .collect(),
+        }
+    }
+
+    fn make_ticks(n: u64) -> Vec<Tick> {
+        (0..n)
+            .map(|i| make_tick(1000 + i, 150.0 + i as f64 * 0.1, 2800.0 + i as f64 * 0.5))
+            .collect()
+    }
+
+    #[test]
+    fn test_single_partial_block_roundtrip() {
+        let ticks = make_ticks(5);
+        let mut writer = BlockWriter::new(Vec::new());
+        for tick in &ticks {
+            writer.push(tick.clone()).unwrap();
+        }
+        let buf = writer.finish().unwrap();
+
+        let mut reader = BlockReader::open(Cursor::new(buf)).unwrap();
+        assert_eq!(reader.num_blocks(), 1);
+
+        let decoded = reader.read_block(0).unwrap();
+        assert_eq!(decoded.len(), ticks.len());
+        for (orig, got) in ticks.iter().zip(decoded.iter()) {
+            assert_eq!(orig.timestamp, got.timestamp);
+        }
+    }
+
+    #[test]
+    fn test_multi_block_index_and_seek() {
+        let ticks = make_ticks(10);
+        let mut writer = BlockWriter::with_flush_every(Vec::new(), 4);
+        for tick in &ticks {
+            writer.push(tick.clone()).unwrap();
+        }
+        let buf = writer.finish().unwrap();
+
+        let mut reader = BlockReader::open(Cursor::new(buf)).unwrap();
+        assert_eq!(reader.num_blocks(), 3);
+
+        let mut all = Vec::new();
+        for i in 0..reader.num_blocks() {
+            let mut block = Vec::new();
+            reader.read_block_into(i, &mut block).unwrap();
+            all.extend(block);
+        }
+        assert_eq!(all.len(), ticks.len());
+        for (orig, got) in ticks.iter().zip(all.iter()) {
+            assert_eq!(orig.timestamp, got.timestamp);
+        }
+
+        let seeked = reader.seek_time(1007).unwrap().unwrap();
+        assert!(seeked.iter().any(|t| t.timestamp == 1007));
+
+        assert!(reader.seek_time(9999).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_corrupted_index_checksum() {
+        let ticks = make_ticks(3);
+        let mut writer = BlockWriter::new(Vec::new());
+        for tick in &ticks {
+            writer.push(tick.clone()).unwrap();
+        }
+        let mut buf = writer.finish().unwrap();
+
+        let len = buf.len();
+        buf[len - FOOTER_LEN - 1] ^= 0xFF;
+
+        let result = BlockReader::open(Cursor::new(buf));
+        assert!(result.is_err());
+    }
+}
@@ -1,26 +1,136 @@
 use std::collections::HashMap;
 use std::fs::File;
-use std::io::{self, Read, Write};
+use std::io::{self, IoSlice, Read, Write};
 use std::path::Path;
 
+use crate::bitstream::{BitReader, BitWriter};
+use crate::codec::{codec_for_id, Codec, Store};
 use crate::crc32::Crc32;
 use crate::delta_encoding::DeltaEncoding;
+use crate::delta_huffman::DeltaHuffmanTable;
+use crate::fsst::SymbolTable;
+use crate::gorilla::GorillaCodec;
 use crate::types::{CompressionMetadata, Tick};
+use crate::varint;
+use crate::zerocopy::{bytes_from_prefix, F64LeSlice, FrameFlags, U32Le, U64Le};
 
 pub struct CompressedTimeSeries {
     version: u8,
+    codec_id: u8,
+    lossless: bool,
+    delta_codec: u8,
     symbols: Vec<String>,
     base_ts: u64,
     ref_frame: Vec<f64>,
     data: Vec<u8>,
+    /// Length of `data` before `codec`'s compression stage, recorded in the
+    /// frame header (rather than left for each codec to smuggle inside its
+    /// own stream) so [`CompressionMetadata`] can report it without
+    /// decompressing first.
+    uncompressed_len: u32,
     num_ticks: u32,
     ref_crc: u32,
     data_crc: u32,
     overall_crc: u32,
 }
 
+/// Builds a [`CompressedTimeSeries`] with a chosen [`Codec`] for the final
+/// entropy stage. Defaults to [`Store`] (no-op) so `CompressedTimeSeries::compress`
+/// keeps its existing behavior.
+pub struct CompressedTimeSeriesBuilder {
+    codec: Box<dyn Codec>,
+    lossless: bool,
+}
+
+impl CompressedTimeSeriesBuilder {
+    pub fn new() -> Self {
+        CompressedTimeSeriesBuilder {
+            codec: Box::new(Store),
+            lossless: false,
+        }
+    }
+
+    pub fn codec(mut self, codec: Box<dyn Codec>) -> Self {
+        self.codec = codec;
+        self
+    }
+
+    /// Opts into exact `f64` round-trips via Gorilla-style XOR float
+    /// encoding instead of the default lossy basis-point quantization.
+    pub fn lossless(mut self, lossless: bool) -> Self {
+        self.lossless = lossless;
+        self
+    }
+
+    pub fn compress(self, ticks: &[Tick]) -> io::Result<CompressedTimeSeries> {
+        CompressedTimeSeries::compress_with(ticks, self.codec.as_ref(), self.lossless)
+    }
+}
+
+impl Default for CompressedTimeSeriesBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// One tick's already-diffed framing: the timestamp delta, the per-symbol
+/// changed-bitmap, and the `(symbol index, raw price, basis-point delta)`
+/// triples for the symbols that changed.
+type TickDelta = (u32, Vec<u8>, Vec<(usize, f64, i32)>);
+
+/// Re-emits the per-tick framing (timestamp delta, change bitmap,
+/// length-prefixed bit-packed payload) for a batch of already computed
+/// per-tick deltas, with `encode_one` supplying the actual per-delta bit
+/// encoding (basis-point [`DeltaEncoding`], Huffman, or Gorilla XOR). Lets
+/// [`CompressedTimeSeries::compress_with`] try more than one delta codec
+/// against the same tick data without redoing the symbol-diffing pass.
+///
+/// Since ticks arrive at a roughly steady cadence, the timestamp delta is
+/// stored delta-of-delta: the running gap between consecutive ticks is
+/// itself diffed against the previous gap, zigzag-mapped to an unsigned
+/// value, and VarInt-packed — a steady cadence collapses to one byte per
+/// tick instead of a fixed 4.
+fn encode_ticks<F>(
+    ticks: &[TickDelta],
+    mut encode_one: F,
+) -> io::Result<Vec<u8>>
+where
+    F: FnMut(&mut BitWriter, usize, f64, i32) -> io::Result<()>,
+{
+    let mut data = Vec::new();
+    let mut prev_ts_delta: u32 = 0;
+    let mut prev_gap: i64 = 0;
+
+    for (ts_delta, bm, deltas) in ticks {
+        let gap = *ts_delta as i64 - prev_ts_delta as i64;
+        let dd = gap - prev_gap;
+        varint::encode_u64(varint::zigzag_encode(dd), &mut data);
+        prev_gap = gap;
+        prev_ts_delta = *ts_delta;
+
+        data.extend_from_slice(bm);
+
+        let mut writer = BitWriter::new();
+        for &(idx, price, delta_bp) in deltas {
+            encode_one(&mut writer, idx, price, delta_bp)?;
+        }
+        let packed = writer.finish();
+        varint::encode_u64(packed.len() as u64, &mut data);
+        data.extend_from_slice(&packed);
+    }
+    Ok(data)
+}
+
 impl CompressedTimeSeries {
+    pub fn builder() -> CompressedTimeSeriesBuilder {
+        CompressedTimeSeriesBuilder::new()
+    }
+
     pub fn compress(ticks: &[Tick]) -> io::Result<Self> {
+        Self::compress_with(ticks, &Store, false)
+    }
+
+    fn compress_with(ticks: &[Tick], codec: &dyn Codec, lossless: bool) -> io::Result<Self> {
         if ticks.is_empty() {
             return Err(io::Error::new(
                 io::ErrorKind::InvalidInput,
@@ -52,23 +162,31 @@ impl CompressedTimeSeries {
         }
         let ref_crc = crc.checksum(&ref_bytes);
 
-        let mut data = Vec::new();
         let mut prev = ref_frame.clone();
+        let mut ticks_deltas: Vec<TickDelta> = Vec::new();
+        let mut all_deltas: Vec<i32> = Vec::new();
 
         for tick in ticks.iter().skip(1) {
             let ts_delta = (tick.timestamp - base_ts) as u32;
-            data.extend_from_slice(&ts_delta.to_le_bytes());
 
             let mut changed = vec![false; n];
             let mut deltas = Vec::new();
 
             for (sym, &price) in &tick.prices {
                 if let Some(&idx) = sym_idx.get(sym) {
-                    let delta_bp = ((price - prev[idx]) / prev[idx] * 10000.0).round() as i32;
-                    if delta_bp != 0 {
-                        changed[idx] = true;
-                        deltas.push((idx, price, delta_bp));
-                        prev[idx] = price;
+                    if lossless {
+                        if price.to_bits() != prev[idx].to_bits() {
+                            changed[idx] = true;
+                            deltas.push((idx, price, 0));
+                            prev[idx] = price;
+                        }
+                    } else {
+                        let delta_bp = ((price - prev[idx]) / prev[idx] * 10000.0).round() as i32;
+                        if delta_bp != 0 {
+                            changed[idx] = true;
+                            deltas.push((idx, price, delta_bp));
+                            prev[idx] = price;
+                        }
                     }
                 }
             }
@@ -80,21 +198,74 @@ impl CompressedTimeSeries {
                     bm[idx / 8] |= 1 << (idx % 8);
                 }
             }
-            data.extend_from_slice(&bm);
 
-            for (_, _, delta_bp) in deltas {
-                DeltaEncoding::from_basis(delta_bp).encode(&mut data);
+            deltas.sort_by_key(|&(idx, _, _)| idx);
+            if !lossless {
+                all_deltas.extend(deltas.iter().map(|&(_, _, bp)| bp));
+            }
+            ticks_deltas.push((ts_delta, bm, deltas));
+        }
+
+        let (delta_codec, delta_table, data) = if lossless {
+            let mut gorilla_states: Vec<GorillaCodec> =
+                ref_frame.iter().map(|&v| GorillaCodec::new(v)).collect();
+            let data = encode_ticks(&ticks_deltas, |writer, idx, price, _| {
+                gorilla_states[idx].encode(writer, price);
+                Ok(())
+            })?;
+            (0u8, None, data)
+        } else {
+            let bitpacked = encode_ticks(&ticks_deltas, |writer, _, _, delta_bp| {
+                DeltaEncoding::from_basis(delta_bp)?.encode(writer);
+                Ok(())
+            })?;
+
+            // The table's on-wire symbol count is a `u16`; an alphabet
+            // bigger than that can't round-trip, so don't even attempt the
+            // Huffman path and fall back to the bitpacked encoding instead.
+            let distinct_deltas: std::collections::HashSet<i32> =
+                all_deltas.iter().copied().collect();
+            if distinct_deltas.len() <= u16::MAX as usize {
+                let table = DeltaHuffmanTable::build(&all_deltas);
+                let encoder = table.encoder();
+                let huffman_payload = encode_ticks(&ticks_deltas, |writer, _, _, delta_bp| {
+                    encoder.encode(delta_bp, writer)
+                })?;
+                let table_bytes = table.serialize()?;
+
+                if table_bytes.len() + huffman_payload.len() < bitpacked.len() {
+                    (1u8, Some(table_bytes), huffman_payload)
+                } else {
+                    (0u8, None, bitpacked)
+                }
+            } else {
+                (0u8, None, bitpacked)
             }
+        };
+
+        let mut data = data;
+        if let Some(table_bytes) = &delta_table {
+            let mut prefixed = Vec::new();
+            varint::encode_u64(table_bytes.len() as u64, &mut prefixed);
+            prefixed.extend_from_slice(table_bytes);
+            prefixed.extend_from_slice(&data);
+            data = prefixed;
         }
 
+        let uncompressed_len = data.len() as u32;
+        let data = codec.compress(&data)?;
         let data_crc = crc.checksum(&data);
 
         Ok(CompressedTimeSeries {
-            version: 1,
+            version: 6,
+            codec_id: codec.id(),
+            lossless,
+            delta_codec,
             symbols,
             base_ts,
             ref_frame,
             data,
+            uncompressed_len,
             num_ticks: ticks.len() as u32,
             ref_crc,
             data_crc,
@@ -123,7 +294,30 @@ impl CompressedTimeSeries {
             ));
         }
 
-        let mut ticks = Vec::with_capacity(self.num_ticks as usize);
+        let codec = codec_for_id(self.codec_id)?;
+        let mut data = codec.decompress(&self.data)?;
+
+        let delta_decoder = if self.delta_codec == 1 {
+            let (table_len, len_bytes) = varint::decode_u64(&data, 0).map_err(|_| {
+                io::Error::new(io::ErrorKind::InvalidData, "Delta Huffman table length missing")
+            })?;
+            let table_len = table_len as usize;
+            if table_len > data.len().saturating_sub(len_bytes) {
+                return Err(io::Error::new(io::ErrorKind::InvalidData, "Delta Huffman table truncated"));
+            }
+            let (table, _) = DeltaHuffmanTable::deserialize(&data[len_bytes..len_bytes + table_len])?;
+            data.drain(0..len_bytes + table_len);
+            Some(table.decoder())
+        } else {
+            None
+        };
+
+        // `self.num_ticks` is attacker-controlled when `self` came from
+        // `deserialize`; even with that parse-time bound, cap the
+        // preallocation at the decompressed payload size (each tick needs
+        // at least one byte there) so this can never over-allocate.
+        let ticks_capacity = (self.num_ticks as usize).min(data.len()) + 1;
+        let mut ticks = Vec::with_capacity(ticks_capacity);
         let n = self.symbols.len();
 
         let mut first = HashMap::new();
@@ -136,28 +330,55 @@ impl CompressedTimeSeries {
         });
 
         let mut curr = self.ref_frame.clone();
+        let mut gorilla_states: Vec<GorillaCodec> =
+            self.ref_frame.iter().map(|&v| GorillaCodec::new(v)).collect();
         let mut pos = 0;
         let bm_bytes = (n + 7) / 8;
+        let mut prev_ts_delta: u32 = 0;
+        let mut prev_gap: i64 = 0;
+
+        while pos < data.len() {
+            let (dd_zigzag, dd_len) = match varint::decode_u64(&data, pos) {
+                Ok(v) => v,
+                Err(_) => break,
+            };
+            pos += dd_len;
 
-        while pos < self.data.len() {
-            if pos + 4 > self.data.len() {
+            if pos + bm_bytes > data.len() {
                 break;
             }
+            let bm = &data[pos..pos + bm_bytes];
+            pos += bm_bytes;
 
-            let ts_delta = u32::from_le_bytes(self.data[pos..pos + 4].try_into().unwrap());
-            pos += 4;
-
-            if pos + bm_bytes > self.data.len() {
+            let (packed_len, len_len) = match varint::decode_u64(&data, pos) {
+                Ok(v) => v,
+                Err(_) => break,
+            };
+            let packed_len = packed_len as usize;
+            pos += len_len;
+            if packed_len > data.len().saturating_sub(pos) {
                 break;
             }
-            let bm = &self.data[pos..pos + bm_bytes];
-            pos += bm_bytes;
+            let mut reader = BitReader::new(&data[pos..pos + packed_len]);
+            pos += packed_len;
+
+            let gap = prev_gap + varint::zigzag_decode(dd_zigzag);
+            let ts_delta = (prev_ts_delta as i64 + gap) as u32;
+            prev_gap = gap;
+            prev_ts_delta = ts_delta;
 
             for idx in 0..n {
                 if bm[idx / 8] & (1 << (idx % 8)) != 0 {
-                    let enc = DeltaEncoding::decode(&self.data, &mut pos)?;
-                    let delta_bp = enc.to_basis();
-                    curr[idx] *= 1.0 + delta_bp as f64 / 10000.0;
+                    if self.lossless {
+                        curr[idx] = gorilla_states[idx].decode(&mut reader)?;
+                    } else {
+                        let delta_bp = if let Some(decoder) = &delta_decoder {
+                            decoder.decode(&mut reader)?
+                        } else {
+                            DeltaEncoding::decode(&mut reader)?.to_basis()
+                        };
+                        curr[idx] *= 1.0 + delta_bp as f64 / 10000.0;
+                    }
                 }
             }
 
@@ -174,35 +395,74 @@ impl CompressedTimeSeries {
         Ok(ticks)
     }
 
-    pub fn serialize(&self) -> io::Result<Vec<u8>> {
-        let crc = Crc32::new();
-        let mut buf = Vec::new();
-
-        buf.write_all(&[self.version])?;
-        buf.write_all(&(self.symbols.len() as u16).to_le_bytes())?;
-        buf.write_all(&self.num_ticks.to_le_bytes())?;
-        buf.write_all(&self.base_ts.to_le_bytes())?;
+    /// Builds the on-disk format as independent, already-encoded byte
+    /// regions: header/metadata, symbol table, reference frame, and the
+    /// checksummed delta block. [`Self::serialize`] concatenates these into
+    /// one buffer; [`Self::write_to`] instead flushes them directly via
+    /// vectored I/O, skipping that concatenation.
+    fn serialize_sections(&self) -> io::Result<Vec<Vec<u8>>> {
+        let mut header = Vec::new();
+        header.write_all(&[self.version])?;
+        header.write_all(&[self.codec_id])?;
+        header.write_all(&[self.lossless as u8])?;
+        header.write_all(&[self.delta_codec])?;
+        varint::encode_u64(self.symbols.len() as u64, &mut header);
+        varint::encode_u64(self.num_ticks as u64, &mut header);
+        header.write_all(&self.base_ts.to_le_bytes())?;
+
+        let mut symbol_section = Vec::new();
+        let sym_bytes: Vec<&[u8]> = self.symbols.iter().map(|s| s.as_bytes()).collect();
+        let symbol_table = SymbolTable::train_bulk(&sym_bytes);
+        let table_bytes = symbol_table.serialize();
+        varint::encode_u64(table_bytes.len() as u64, &mut symbol_section);
+        symbol_section.write_all(&table_bytes)?;
 
         for sym in &self.symbols {
-            buf.write_all(&[sym.len() as u8])?;
-            buf.write_all(sym.as_bytes())?;
+            let compressed = symbol_table.compress_symbols(sym.as_bytes());
+            varint::encode_u64(compressed.len() as u64, &mut symbol_section);
+            symbol_section.write_all(&compressed)?;
         }
 
+        let mut ref_raw = Vec::new();
         for &p in &self.ref_frame {
-            buf.write_all(&p.to_le_bytes())?;
+            ref_raw.write_all(&p.to_le_bytes())?;
         }
+        let ref_compressed = codec_for_id(self.codec_id)?.compress(&ref_raw)?;
+
+        let mut ref_section = Vec::new();
+        varint::encode_u64(ref_compressed.len() as u64, &mut ref_section);
+        ref_section.write_all(&ref_compressed)?;
 
-        buf.write_all(&self.ref_crc.to_le_bytes())?;
-        buf.write_all(&self.data_crc.to_le_bytes())?;
-        buf.write_all(&(self.data.len() as u32).to_le_bytes())?;
-        buf.write_all(&self.data)?;
+        let mut data_section = Vec::new();
+        data_section.write_all(&self.ref_crc.to_le_bytes())?;
+        data_section.write_all(&self.data_crc.to_le_bytes())?;
+        varint::encode_u64(self.uncompressed_len as u64, &mut data_section);
+        varint::encode_u64(self.data.len() as u64, &mut data_section);
+        data_section.write_all(&self.data)?;
+
+        Ok(vec![header, symbol_section, ref_section, data_section])
+    }
+
+    pub fn serialize(&self) -> io::Result<Vec<u8>> {
+        let crc = Crc32::new();
+        let sections = self.serialize_sections()?;
+
+        let mut buf = Vec::new();
+        for section in &sections {
+            buf.extend_from_slice(section);
+        }
 
         let overall_crc = crc.checksum(&buf);
-        buf.write_all(&overall_crc.to_le_bytes())?;
+        buf.extend_from_slice(&overall_crc.to_le_bytes());
 
         Ok(buf)
     }
 
+    /// Parses the on-disk format written by [`Self::serialize`]/[`Self::write_to`].
+    /// Every fixed-width field goes through a [`crate::zerocopy`] bounds-checked
+    /// view and every VarInt-prefixed region through [`bytes_from_prefix`], so a
+    /// truncated or maliciously-crafted blob yields `InvalidData`/`UnexpectedEof`
+    /// instead of panicking on an out-of-range slice.
     pub fn deserialize(data: &[u8]) -> io::Result<Self> {
         let crc = Crc32::new();
 
@@ -211,7 +471,8 @@ impl CompressedTimeSeries {
         }
 
         let crc_pos = data.len() - 4;
-        let overall_crc = u32::from_le_bytes(data[crc_pos..].try_into().unwrap());
+        let (overall_crc_view, _) = U32Le::ref_from_prefix(&data[crc_pos..])?;
+        let overall_crc = overall_crc_view.get();
 
         if crc.checksum(&data[..crc_pos]) != overall_crc {
             return Err(io::Error::new(
@@ -221,51 +482,106 @@ impl CompressedTimeSeries {
         }
 
         let mut pos = 0;
-        let version = data[pos];
-        pos += 1;
-
-        let n = u16::from_le_bytes(data[pos..pos + 2].try_into().unwrap()) as usize;
-        pos += 2;
-
-        let num_ticks = u32::from_le_bytes(data[pos..pos + 4].try_into().unwrap());
-        pos += 4;
+        let (flags, _) = FrameFlags::ref_from_prefix(&data[pos..])?;
+        let version = flags.version;
+        let codec_id = flags.codec_id;
+        let lossless = flags.lossless != 0;
+        let delta_codec = flags.delta_codec;
+        pos += std::mem::size_of::<FrameFlags>();
+
+        let (n, n_len) = varint::decode_u64(data, pos)?;
+        let n = n as usize;
+        pos += n_len;
+
+        let (num_ticks, num_ticks_len) = varint::decode_u64(data, pos)?;
+        let num_ticks = num_ticks as u32;
+        pos += num_ticks_len;
+
+        // Bound against the remaining buffer the same way `n` (the symbol
+        // count) is below — `num_ticks` otherwise flows unchecked into
+        // `decompress`'s `Vec::with_capacity`, where a crafted blob can
+        // claim billions of ticks and abort the process on the allocation.
+        if num_ticks as usize > data.len().saturating_sub(pos) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "Tick count exceeds remaining data",
+            ));
+        }
 
-        let base_ts = u64::from_le_bytes(data[pos..pos + 8].try_into().unwrap());
-        pos += 8;
+        let (base_ts_view, _) = U64Le::ref_from_prefix(&data[pos..])?;
+        let base_ts = base_ts_view.get();
+        pos += std::mem::size_of::<U64Le>();
+
+        let (table_len, table_len_len) = varint::decode_u64(data, pos)?;
+        let table_len = table_len as usize;
+        pos += table_len_len;
+        let (table_bytes, _) = bytes_from_prefix(&data[pos..], table_len)?;
+        let (symbol_table, _) = SymbolTable::deserialize(table_bytes)?;
+        pos += table_len;
+
+        // Each symbol needs at least one byte for its length VarInt, so `n`
+        // can't exceed the bytes left in `data` — bound it before trusting
+        // it as a `Vec::with_capacity` argument, the same way `table_len`
+        // (above) and `comp_len` (below) are bounds-checked via
+        // `bytes_from_prefix`.
+        if n > data.len().saturating_sub(pos) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "Symbol count exceeds remaining data",
+            ));
+        }
 
         let mut symbols = Vec::with_capacity(n);
         for _ in 0..n {
-            let len = data[pos] as usize;
-            pos += 1;
-            let sym = String::from_utf8(data[pos..pos + len].to_vec())
-                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+            let (len, len_len) = varint::decode_u64(data, pos)?;
+            let len = len as usize;
+            pos += len_len;
+            let (sym_bytes, _) = bytes_from_prefix(&data[pos..], len)?;
+            let raw = symbol_table.decompress_symbols(sym_bytes)?;
+            let sym = String::from_utf8(raw).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
             symbols.push(sym);
             pos += len;
         }
 
-        let mut ref_frame = Vec::with_capacity(n);
-        for _ in 0..n {
-            ref_frame.push(f64::from_le_bytes(data[pos..pos + 8].try_into().unwrap()));
-            pos += 8;
-        }
+        let (ref_len, ref_len_len) = varint::decode_u64(data, pos)?;
+        let ref_len = ref_len as usize;
+        pos += ref_len_len;
+        let (ref_compressed, _) = bytes_from_prefix(&data[pos..], ref_len)?;
+        let ref_raw = codec_for_id(codec_id)?.decompress(ref_compressed)?;
+        pos += ref_len;
+
+        let (ref_frame_view, _) = F64LeSlice::ref_from_prefix(&ref_raw, n)?;
+        let ref_frame = ref_frame_view.to_vec();
+
+        let (ref_crc_view, _) = U32Le::ref_from_prefix(&data[pos..])?;
+        let ref_crc = ref_crc_view.get();
+        pos += std::mem::size_of::<U32Le>();
 
-        let ref_crc = u32::from_le_bytes(data[pos..pos + 4].try_into().unwrap());
-        pos += 4;
+        let (data_crc_view, _) = U32Le::ref_from_prefix(&data[pos..])?;
+        let data_crc = data_crc_view.get();
+        pos += std::mem::size_of::<U32Le>();
 
-        let data_crc = u32::from_le_bytes(data[pos..pos + 4].try_into().unwrap());
-        pos += 4;
+        let (uncompressed_len, uncompressed_len_len) = varint::decode_u64(data, pos)?;
+        let uncompressed_len = uncompressed_len as u32;
+        pos += uncompressed_len_len;
 
-        let comp_len = u32::from_le_bytes(data[pos..pos + 4].try_into().unwrap()) as usize;
-        pos += 4;
+        let (comp_len, comp_len_len) = varint::decode_u64(data, pos)?;
+        let comp_len = comp_len as usize;
+        pos += comp_len_len;
 
-        let comp_data = data[pos..pos + comp_len].to_vec();
+        let (comp_data, _) = bytes_from_prefix(&data[pos..], comp_len)?;
+        let comp_data = comp_data.to_vec();
 
         Ok(CompressedTimeSeries {
             version,
+            codec_id,
+            lossless,
+            delta_codec,
             symbols,
             base_ts,
             ref_frame,
             data: comp_data,
+            uncompressed_len,
             num_ticks,
             ref_crc,
             data_crc,
@@ -280,7 +596,7 @@ impl CompressedTimeSeries {
 
     pub fn write_to_file<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
         let mut file = File::create(path)?;
-        file.write_all(&self.serialize()?)?;
+        self.write_to(&mut file)?;
         file.sync_all()
     }
 
@@ -290,10 +606,38 @@ impl CompressedTimeSeries {
         Self::deserialize(&buf)
     }
 
+    /// Flushes the serialized format straight from its component sections
+    /// via `write_vectored`, without first concatenating them into one
+    /// buffer the way [`Self::serialize`] does.
     pub fn write_to<W: Write>(&self, w: &mut W) -> io::Result<usize> {
-        let ser = self.serialize()?;
-        w.write_all(&ser)?;
-        Ok(ser.len())
+        let crc = Crc32::new();
+        let sections = self.serialize_sections()?;
+        let section_refs: Vec<&[u8]> = sections.iter().map(|s| s.as_slice()).collect();
+        let overall_crc = crc.checksum_multi(&section_refs);
+        let crc_bytes = overall_crc.to_le_bytes();
+
+        let mut slices_owned: Vec<IoSlice> = sections
+            .iter()
+            .map(|s| IoSlice::new(s))
+            .chain(std::iter::once(IoSlice::new(&crc_bytes)))
+            .collect();
+        let mut slices: &mut [IoSlice] = &mut slices_owned;
+
+        let total: usize = slices.iter().map(|s| s.len()).sum();
+        let mut written = 0;
+        while written < total {
+            let n = w.write_vectored(slices)?;
+            if n == 0 {
+                return Err(io::Error::new(
+                    io::ErrorKind::WriteZero,
+                    "failed to write whole buffer",
+                ));
+            }
+            written += n;
+            IoSlice::advance_slices(&mut slices, n);
+        }
+
+        Ok(total)
     }
 
     pub fn read_from<R: Read>(r: &mut R) -> io::Result<Self> {
@@ -310,14 +654,39 @@ impl CompressedTimeSeries {
         Self::deserialize(blob)
     }
 
+    /// The codec-compressed byte stream as serialized, before any codec
+    /// decoding. Only used by [`crate::stream`]'s tests to feed a known-good
+    /// compressed payload through `codec.decompress` ahead of
+    /// [`crate::stream::TickDecoder`], without re-deriving it by hand.
+    #[cfg(test)]
+    pub(crate) fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Builds an incremental [`crate::stream::TickDecoder`] seeded from this
+    /// series' reference frame, ready to accept pushed delta-stream chunks.
+    pub fn tick_decoder(&self) -> crate::stream::TickDecoder {
+        crate::stream::TickDecoder::with_delta_codec(
+            self.symbols.clone(),
+            self.ref_frame.clone(),
+            self.base_ts,
+            self.lossless,
+            self.delta_codec,
+        )
+    }
+
     pub fn metadata(&self) -> CompressionMetadata {
         CompressionMetadata {
             version: self.version,
+            codec_id: self.codec_id,
+            lossless: self.lossless,
+            delta_codec: self.delta_codec,
             num_symbols: self.symbols.len(),
             num_ticks: self.num_ticks as usize,
             base_timestamp: self.base_ts,
             symbols: self.symbols.clone(),
             compressed_size: self.data.len(),
+            uncompressed_size: self.uncompressed_len as usize,
             reference_checksum: self.ref_crc,
             data_checksum: self.data_crc,
             overall_checksum: self.overall_crc,
@@ -438,17 +807,178 @@ mod tests {
         assert_eq!(compressed.symbols, restored.symbols);
     }
 
+    #[test]
+    fn test_builder_default_codec_is_store() {
+        let ticks = make_ticks();
+        let compressed = CompressedTimeSeries::builder().compress(&ticks).unwrap();
+        assert_eq!(compressed.metadata().codec_id, crate::codec::Store.id());
+
+        let decompressed = compressed.decompress().unwrap();
+        assert_eq!(decompressed.len(), ticks.len());
+    }
+
+    #[test]
+    fn test_lz4_codec_compresses_reference_frame_too() {
+        let ticks = make_ticks();
+        let compressed = CompressedTimeSeries::builder()
+            .codec(Box::new(crate::lz4::Lz4))
+            .compress(&ticks)
+            .unwrap();
+
+        let serialized = compressed.serialize().unwrap();
+        let restored = CompressedTimeSeries::deserialize(&serialized).unwrap();
+        assert_eq!(compressed.ref_frame, restored.ref_frame);
+
+        let meta = compressed.metadata();
+        assert!(meta.uncompressed_size > 0);
+        assert_eq!(meta.uncompressed_size, restored.metadata().uncompressed_size);
+
+        let decompressed = restored.decompress().unwrap();
+        assert_eq!(decompressed.len(), ticks.len());
+    }
+
     #[test]
     fn test_metadata() {
         let ticks = make_ticks();
         let compressed = CompressedTimeSeries::compress(&ticks).unwrap();
         let meta = compressed.metadata();
 
-        assert_eq!(meta.version, 1);
+        assert_eq!(meta.version, 6);
         assert_eq!(meta.num_symbols, 2);
         assert_eq!(meta.num_ticks, 3);
         assert_eq!(meta.base_timestamp, 1000);
         assert_eq!(meta.symbols.len(), 2);
+        assert!(!meta.lossless);
+        assert_eq!(meta.delta_codec, 0);
+    }
+
+    #[test]
+    fn test_huffman_delta_codec_wins_on_skewed_deltas() {
+        // Eight symbols all nudge by the same +1bp every tick (highly
+        // skewed, multi-symbol-per-tick distribution), so the per-tick
+        // bit-packed buffer holds several Huffman-coded deltas instead of
+        // just one — letting the 1-bit-per-delta savings outweigh the
+        // per-tick byte-rounding and Huffman table overhead.
+        let symbols = ["A", "B", "C", "D", "E", "F", "G", "H"];
+        let mut prices: HashMap<String, f64> =
+            symbols.iter().map(|s| (s.to_string(), 100.0)).collect();
+
+        let mut ticks = vec![Tick {
+            timestamp: 1000,
+            prices: prices.clone(),
+        }];
+        for i in 1..200u64 {
+            for sym in &symbols {
+                let p = prices.get_mut(*sym).unwrap();
+                *p *= 1.0001;
+            }
+            ticks.push(Tick {
+                timestamp: 1000 + i,
+                prices: prices.clone(),
+            });
+        }
+
+        let compressed = CompressedTimeSeries::compress(&ticks).unwrap();
+        assert_eq!(compressed.metadata().delta_codec, 1);
+
+        let decompressed = compressed.decompress().unwrap();
+        assert_eq!(decompressed.len(), ticks.len());
+        for (orig, decomp) in ticks.iter().zip(decompressed.iter()) {
+            for sym in &symbols {
+                let orig_price = orig.prices[*sym];
+                let decomp_price = decomp.prices[*sym];
+                let rel_error = ((orig_price - decomp_price) / orig_price).abs();
+                assert!(rel_error < 0.01);
+            }
+        }
+    }
+
+    #[test]
+    fn test_huffman_delta_codec_skipped_for_oversized_alphabet() {
+        // `DeltaHuffmanTable`'s on-wire symbol count is a `u16`, so an
+        // alphabet of more than 65535 distinct basis-point deltas must fall
+        // back to the plain bitpacked encoding instead of selecting the
+        // Huffman path. Every real delta is immediately followed by a
+        // correction back to a fixed baseline, so the single symbol's price
+        // never drifts or overflows across the 69,999-step walk.
+        let baseline = 100.0;
+        let mut cur = baseline;
+        let mut ticks = vec![Tick {
+            timestamp: 0,
+            prices: [("A".to_string(), baseline)].into_iter().collect(),
+        }];
+
+        for i in 1..70_000i64 {
+            let next = cur * (1.0 + i as f64 / 10000.0);
+            ticks.push(Tick {
+                timestamp: i as u64 * 2,
+                prices: [("A".to_string(), next)].into_iter().collect(),
+            });
+            ticks.push(Tick {
+                timestamp: i as u64 * 2 + 1,
+                prices: [("A".to_string(), baseline)].into_iter().collect(),
+            });
+            cur = baseline;
+        }
+
+        let compressed = CompressedTimeSeries::compress(&ticks).unwrap();
+        assert_eq!(compressed.metadata().delta_codec, 0);
+
+        let decompressed = compressed.decompress().unwrap();
+        assert_eq!(decompressed.len(), ticks.len());
+    }
+
+    #[test]
+    fn test_lossless_roundtrip_exact() {
+        let ticks = vec![
+            Tick {
+                timestamp: 1000,
+                prices: [("AAPL", 150.123456789), ("GOOGL", 2800.0001)]
+                    .iter()
+                    .map(|(k, v)| (k.to_string(), *v))
+                    .collect(),
+            },
+            Tick {
+                timestamp: 1001,
+                prices: [("AAPL", 150.1234567890123), ("GOOGL", 2800.0001)]
+                    .iter()
+                    .map(|(k, v)| (k.to_string(), *v))
+                    .collect(),
+            },
+            Tick {
+                timestamp: 1002,
+                prices: [("AAPL", 0.1 + 0.2), ("GOOGL", 2799.9999)]
+                    .iter()
+                    .map(|(k, v)| (k.to_string(), *v))
+                    .collect(),
+            },
+        ];
+
+        let compressed = CompressedTimeSeries::builder()
+            .lossless(true)
+            .compress(&ticks)
+            .unwrap();
+        assert!(compressed.metadata().lossless);
+
+        let decompressed = compressed.decompress().unwrap();
+        assert_eq!(ticks.len(), decompressed.len());
+        for (orig, decomp) in ticks.iter().zip(decompressed.iter()) {
+            for (sym, &price) in &orig.prices {
+                let decomp_price = *decomp.prices.get(sym).unwrap();
+                assert_eq!(price.to_bits(), decomp_price.to_bits());
+            }
+        }
+
+        let serialized = compressed.serialize().unwrap();
+        let restored = CompressedTimeSeries::deserialize(&serialized).unwrap();
+        assert!(restored.metadata().lossless);
+        let redecompressed = restored.decompress().unwrap();
+        for (orig, decomp) in ticks.iter().zip(redecompressed.iter()) {
+            for (sym, &price) in &orig.prices {
+                let decomp_price = *decomp.prices.get(sym).unwrap();
+                assert_eq!(price.to_bits(), decomp_price.to_bits());
+            }
+        }
     }
 
     #[test]
@@ -474,6 +1004,42 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_deserialize_truncated_header_does_not_panic() {
+        let ticks = make_ticks();
+        let compressed = CompressedTimeSeries::compress(&ticks).unwrap();
+        let serialized = compressed.serialize().unwrap();
+
+        for len in 0..8 {
+            let result = CompressedTimeSeries::deserialize(&serialized[..len]);
+            assert!(result.is_err());
+        }
+    }
+
+    #[test]
+    fn test_deserialize_oversized_length_field_does_not_panic() {
+        let ticks = make_ticks();
+        let compressed = CompressedTimeSeries::compress(&ticks).unwrap();
+        let mut serialized = compressed.serialize().unwrap();
+
+        // The symbol-table length VarInt sits right after the 4 flag bytes
+        // plus the symbol-count and tick-count VarInts (each 1 byte for this
+        // tiny fixture) and the 8-byte base timestamp. Replacing it with a
+        // too-large one-byte VarInt must error instead of panicking on an
+        // out-of-range slice.
+        let table_len_pos = 4 + 1 + 1 + 8;
+        serialized[table_len_pos] = 0x7F;
+
+        // Re-seal the overall CRC so the oversized length, not the
+        // checksum check, is what `deserialize` runs into.
+        let crc_pos = serialized.len() - 4;
+        let overall_crc = Crc32::new().checksum(&serialized[..crc_pos]);
+        serialized[crc_pos..].copy_from_slice(&overall_crc.to_le_bytes());
+
+        let result = CompressedTimeSeries::deserialize(&serialized);
+        assert!(result.is_err());
+    }
+
     #[test]
     fn test_single_tick() {
         let ticks = vec![Tick {
@@ -29,6 +29,20 @@ impl Crc32 {
         }
         !crc
     }
+
+    /// Checksums several byte slices as if they were concatenated, without
+    /// actually concatenating them. Lets callers checksum scatter-gather
+    /// buffers in place.
+    pub fn checksum_multi(&self, chunks: &[&[u8]]) -> u32 {
+        let mut crc = 0xFFFFFFFF;
+        for chunk in chunks {
+            for &byte in *chunk {
+                let i = ((crc ^ byte as u32) & 0xFF) as usize;
+                crc = (crc >> 8) ^ self.table[i];
+            }
+        }
+        !crc
+    }
 }
 
 impl Default for Crc32 {
@@ -48,4 +62,13 @@ mod tests {
         let checksum = crc.checksum(&data);
         assert_eq!(checksum, 0xBA787D5F)
     }
+
+    #[test]
+    fn test_checksum_multi_matches_concatenated() {
+        let crc = Crc32::new();
+        let a: Vec<u8> = vec![0xC0, 0xFF];
+        let b: Vec<u8> = vec![0xEE];
+        let whole: Vec<u8> = a.iter().chain(b.iter()).copied().collect();
+        assert_eq!(crc.checksum_multi(&[&a, &b]), crc.checksum(&whole));
+    }
 }
@@ -32,11 +32,15 @@ impl<Context> bincode::Decode<Context> for Blob {
 
 const OBJECTS_DIR: &str = ".cndl/objects";
 
+fn hash_bytes(data: &[u8]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(data);
+    format!("{:x}", hasher.finalize())
+}
+
 fn write_and_hash_object<T: Encode>(data: &T) -> Result<String, Box<dyn Error>> {
     let sdata = encode_to_vec(data, config::standard())?;
-    let mut hasher = Sha256::new();
-    hasher.update(&sdata);
-    let hash = format!("{:x}", hasher.finalize());
+    let hash = hash_bytes(&sdata);
 
     let (prefix, fname) = hash.split_at(2);
     let dir = Path::new(OBJECTS_DIR).join(prefix);
@@ -48,6 +52,40 @@ fn write_and_hash_object<T: Encode>(data: &T) -> Result<String, Box<dyn Error>>
     Ok(hash)
 }
 
+/// Reads the raw encoded bytes stored for `hash`, the reverse lookup of
+/// [`write_and_hash_object`]'s `split_at(2)` fan-out directory layout.
+/// `hash` may come from a tampered or dangling link (a commit's
+/// `parent_hash`, a snapshot's `blob_hash`, ...), so it's validated as
+/// well-formed hex before `split_at(2)` ever runs, instead of panicking on
+/// a too-short or non-ASCII string.
+pub(crate) fn read_object_bytes(hash: &str) -> Result<Vec<u8>, Box<dyn Error>> {
+    if hash.len() < 2 || !hash.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return Err(format!("malformed object hash {hash:?}").into());
+    }
+
+    let (prefix, fname) = hash.split_at(2);
+    let path = Path::new(OBJECTS_DIR).join(prefix).join(fname);
+    Ok(fs::read(path)?)
+}
+
+/// Re-hashes `hash`'s stored bytes and confirms they still match the
+/// content address they're filed under, then decodes them as `T`. Used by
+/// [`crate::history`] to detect objects tampered with (or corrupted)
+/// without going through `write_and_hash_object` again.
+pub(crate) fn read_verified_object<T: Decode<()>>(hash: &str) -> Result<T, Box<dyn Error>> {
+    let sdata = read_object_bytes(hash)?;
+    let actual_hash = hash_bytes(&sdata);
+    if actual_hash != hash {
+        return Err(format!(
+            "object {hash} is corrupted: content hashes to {actual_hash}"
+        )
+        .into());
+    }
+
+    let (value, _) = bincode::decode_from_slice(&sdata, config::standard())?;
+    Ok(value)
+}
+
 pub fn write_blob_object(data: &Blob) -> Result<String, Box<dyn Error>> {
     write_and_hash_object(data)
 }
@@ -70,6 +108,10 @@ pub fn write_snapshot_object(data: &Snapshot) -> Result<String, Box<dyn Error>>
 #[derive(Debug, Encode, Decode)]
 pub struct Commit {
     pub tree_hash: String,
+    /// [`crate::merkle_root`] over the snapshot's entries, letting a light
+    /// client verify a single ticker's blob against this commit without
+    /// downloading the whole `Snapshot`.
+    pub merkle_root: [u8; 32],
     pub parent_hash: Option<String>,
     pub timestamp: u64,
     pub author: String,
@@ -1,5 +1,7 @@
 use std::io;
 
+use crate::bitstream::{BitReader, BitWriter};
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum DeltaEncoding {
     Tiny(i8),
@@ -30,74 +32,48 @@ impl DeltaEncoding {
         }
     }
 
-    pub fn encode(&self, buf: &mut Vec<u8>) {
+    /// Packs the 2-bit prefix and variable payload contiguously at the bit
+    /// level, so consecutive `Tiny` codes share bytes instead of each
+    /// burning a whole one.
+    pub fn encode(&self, writer: &mut BitWriter) {
         match self {
             DeltaEncoding::Tiny(v) => {
-                // Pack into 4 bits with 0b00 prefix
-                // @ToDo -> Maybe pack two of these together?
-                buf.push((*v as u8) & 0x0F);
+                writer.write_bits(0b00, 2);
+                writer.write_bits((*v as u8 & 0x0F) as u32, 4);
             }
             DeltaEncoding::Small(v) => {
-                // 0b01 prefix + remaining bits + 8 bits
-                buf.push(0b01000000 | ((*v as u8) & 0x3F));
-                buf.push(((*v >> 6) as u8) & 0xFF);
+                writer.write_bits(0b01, 2);
+                writer.write_bits((*v as u16 & 0x3FFF) as u32, 14);
             }
             DeltaEncoding::Large(v) => {
-                // 0b11 prefix + 32 bits
-                buf.push(0b11000000);
-                buf.extend_from_slice(&v.to_le_bytes());
+                writer.write_bits(0b11, 2);
+                writer.write_bits(*v as u32, 32);
             }
         }
     }
 
-    pub fn decode(buf: &[u8], pos: &mut usize) -> io::Result<Self> {
-        if *pos >= buf.len() {
-            return Err(io::Error::new(
-                io::ErrorKind::UnexpectedEof,
-                "Buffer underrun",
-            ));
-        }
-
-        let first = buf[*pos];
-        let pre = first >> 6;
+    pub fn decode(reader: &mut BitReader) -> io::Result<Self> {
+        let prefix = reader.read_bits(2)?;
 
-        match pre {
-            // Tiny
+        match prefix {
             0b00 => {
-                let v = (first & 0x0F) as i8;
-                let v = if v > 7 { v - 16 } else { v };
-                *pos += 1;
+                let raw = reader.read_bits(4)? as u8;
+                let v = if raw > 7 { raw as i8 - 16 } else { raw as i8 };
                 Ok(DeltaEncoding::Tiny(v))
             }
-            // Small
             0b01 => {
-                if *pos + 1 >= buf.len() {
-                    return Err(io::Error::new(
-                        io::ErrorKind::UnexpectedEof,
-                        "Buffer underrun",
-                    ));
-                }
-                let l = (first & 0x3F) as i16;
-                let h = buf[*pos + 1] as i16;
-                let v = (h << 6) | l;
-                let v = if v > 8191 { v - 16384 } else { v };
-                *pos += 2;
-                Ok(DeltaEncoding::Small(v))
+                let raw = reader.read_bits(14)? as u16;
+                let v = if raw > 8191 { raw as i32 - 16384 } else { raw as i32 };
+                Ok(DeltaEncoding::Small(v as i16))
             }
-            // Large
-            _ => {
-                if *pos + 4 >= buf.len() {
-                    return Err(io::Error::new(
-                        io::ErrorKind::UnexpectedEof,
-                        "Buffer underrun",
-                    ));
-                }
-                *pos += 1;
-                let mut bytes = [0u8; 4];
-                bytes.copy_from_slice(&buf[*pos..*pos + 4]);
-                *pos += 4;
-                Ok(DeltaEncoding::Large(i32::from_le_bytes(bytes)))
+            0b11 => {
+                let raw = reader.read_bits(32)?;
+                Ok(DeltaEncoding::Large(raw as i32))
             }
+            _ => Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "Invalid delta prefix",
+            )),
         }
     }
 }
@@ -108,9 +84,48 @@ mod tests {
 
     #[test]
     fn test_delta_encoding() {
-        let buf = [0b00111111];
-        let mut pos: usize = 0;
-        let res = DeltaEncoding::decode(&buf, &mut pos).unwrap();
+        let mut writer = BitWriter::new();
+        DeltaEncoding::Tiny(-1).encode(&mut writer);
+        let bytes = writer.finish();
+
+        let mut reader = BitReader::new(&bytes);
+        let res = DeltaEncoding::decode(&mut reader).unwrap();
         assert_eq!(res, DeltaEncoding::Tiny(-1))
     }
+
+    #[test]
+    fn test_delta_encoding_packs_tiny_codes_contiguously() {
+        // Two Tiny codes (2-bit prefix + 4-bit payload each) take 12 bits
+        // total: the second code starts in the low 2 bits of the first
+        // byte, and the pair fits in 2 bytes.
+        let mut writer = BitWriter::new();
+        DeltaEncoding::Tiny(3).encode(&mut writer);
+        DeltaEncoding::Tiny(-2).encode(&mut writer);
+        let bytes = writer.finish();
+
+        assert_eq!(bytes.len(), 2);
+
+        let mut reader = BitReader::new(&bytes);
+        assert_eq!(DeltaEncoding::decode(&mut reader).unwrap(), DeltaEncoding::Tiny(3));
+        assert_eq!(DeltaEncoding::decode(&mut reader).unwrap(), DeltaEncoding::Tiny(-2));
+    }
+
+    #[test]
+    fn test_delta_encoding_roundtrip_all_variants() {
+        let mut writer = BitWriter::new();
+        let values = [
+            DeltaEncoding::Tiny(-8),
+            DeltaEncoding::Small(8191),
+            DeltaEncoding::Large(i32::MAX),
+        ];
+        for v in &values {
+            v.encode(&mut writer);
+        }
+        let bytes = writer.finish();
+
+        let mut reader = BitReader::new(&bytes);
+        for v in &values {
+            assert_eq!(DeltaEncoding::decode(&mut reader).unwrap(), *v);
+        }
+    }
 }
@@ -0,0 +1,245 @@
+use std::collections::HashMap;
+use std::io;
+
+use crate::bitstream::{BitReader, BitWriter};
+
+const MAX_CODE_LEN: usize = 15;
+
+/// Canonical Huffman table over the distinct basis-point delta values seen
+/// across a series, mirroring [`crate::huffman::Huffman`]'s length-limited
+/// merge/redistribute construction but keyed by an arbitrary `i32` alphabet
+/// (the observed deltas) instead of a fixed 256-byte one. Only the
+/// `(symbol, code_length)` pairs need to be persisted; codes themselves are
+/// canonical and reconstructed on decode.
+pub struct DeltaHuffmanTable {
+    symbols: Vec<i32>,
+    lengths: Vec<u8>,
+}
+
+impl DeltaHuffmanTable {
+    /// Histograms `deltas` and builds the length-limited canonical table.
+    pub fn build(deltas: &[i32]) -> Self {
+        let mut freq: HashMap<i32, u64> = HashMap::new();
+        for &d in deltas {
+            *freq.entry(d).or_insert(0) += 1;
+        }
+
+        let mut symbols: Vec<i32> = freq.keys().copied().collect();
+        symbols.sort_unstable();
+        let freqs: Vec<u64> = symbols.iter().map(|s| freq[s]).collect();
+        let lengths = crate::huffman_core::build_code_lengths(&freqs, MAX_CODE_LEN);
+
+        DeltaHuffmanTable { symbols, lengths }
+    }
+
+    pub fn encoder(&self) -> DeltaHuffmanEncoder {
+        DeltaHuffmanEncoder::from_table(&self.symbols, &self.lengths)
+    }
+
+    pub fn decoder(&self) -> DeltaHuffmanDecoder {
+        DeltaHuffmanDecoder::from_table(&self.symbols, &self.lengths)
+    }
+
+    /// Serializes the `(symbol, length)` pairs as a length-prefixed table:
+    /// `u16` count followed by `i32 symbol + u8 length` per entry. Errors
+    /// out rather than silently truncating if the alphabet is too large for
+    /// the `u16` count to represent.
+    pub fn serialize(&self) -> io::Result<Vec<u8>> {
+        if self.symbols.len() > u16::MAX as usize {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "Delta Huffman table has too many distinct symbols to serialize",
+            ));
+        }
+
+        let mut out = Vec::new();
+        out.extend_from_slice(&(self.symbols.len() as u16).to_le_bytes());
+        for (&sym, &len) in self.symbols.iter().zip(self.lengths.iter()) {
+            out.extend_from_slice(&sym.to_le_bytes());
+            out.push(len);
+        }
+        Ok(out)
+    }
+
+    /// Deserializes a table written by [`Self::serialize`], returning it
+    /// along with the number of bytes consumed.
+    pub fn deserialize(data: &[u8]) -> io::Result<(Self, usize)> {
+        if data.len() < 2 {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "Delta Huffman table too short"));
+        }
+        let n = u16::from_le_bytes(data[0..2].try_into().unwrap()) as usize;
+        let mut pos = 2;
+
+        let mut symbols = Vec::with_capacity(n);
+        let mut lengths = Vec::with_capacity(n);
+        for _ in 0..n {
+            if pos + 5 > data.len() {
+                return Err(io::Error::new(io::ErrorKind::InvalidData, "Delta Huffman table truncated"));
+            }
+            symbols.push(i32::from_le_bytes(data[pos..pos + 4].try_into().unwrap()));
+            lengths.push(data[pos + 4]);
+            pos += 5;
+        }
+
+        Ok((DeltaHuffmanTable { symbols, lengths }, pos))
+    }
+}
+
+/// Per-symbol `(code, length)` lookup built by sorting symbols by
+/// `(code_length, symbol)` and assigning consecutive codes within each
+/// length, same as [`crate::huffman::Huffman`]'s canonical table.
+pub struct DeltaHuffmanEncoder {
+    codes: HashMap<i32, (u32, u8)>,
+}
+
+impl DeltaHuffmanEncoder {
+    fn from_table(symbols: &[i32], lengths: &[u8]) -> Self {
+        let mut order: Vec<usize> = (0..symbols.len()).filter(|&i| lengths[i] > 0).collect();
+        order.sort_by_key(|&i| (lengths[i], symbols[i]));
+
+        let mut codes = HashMap::new();
+        let mut code = 0u32;
+        let mut prev_len = 0u8;
+        for i in order {
+            let len = lengths[i];
+            code <<= len - prev_len;
+            codes.insert(symbols[i], (code, len));
+            code += 1;
+            prev_len = len;
+        }
+        DeltaHuffmanEncoder { codes }
+    }
+
+    pub fn encode(&self, value: i32, writer: &mut BitWriter) -> io::Result<()> {
+        let (code, len) = *self.codes.get(&value).ok_or_else(|| {
+            io::Error::new(io::ErrorKind::InvalidInput, "delta value absent from Huffman table")
+        })?;
+        writer.write_bits(code, len);
+        Ok(())
+    }
+}
+
+/// The inflate-style decode tables generalized to an `i32` alphabet:
+/// `counts[len]` holds how many codes have that length, `symbols[]` holds
+/// symbols ordered by `(length, symbol)`.
+pub struct DeltaHuffmanDecoder {
+    counts: [u16; MAX_CODE_LEN + 1],
+    symbols: Vec<i32>,
+}
+
+impl DeltaHuffmanDecoder {
+    fn from_table(symbols_in: &[i32], lengths: &[u8]) -> Self {
+        let mut counts = [0u16; MAX_CODE_LEN + 1];
+        for &len in lengths {
+            if len > 0 {
+                counts[len as usize] += 1;
+            }
+        }
+
+        let mut order: Vec<usize> = (0..symbols_in.len()).filter(|&i| lengths[i] > 0).collect();
+        order.sort_by_key(|&i| (lengths[i], symbols_in[i]));
+        let symbols = order.into_iter().map(|i| symbols_in[i]).collect();
+
+        DeltaHuffmanDecoder { counts, symbols }
+    }
+
+    pub fn decode(&self, reader: &mut BitReader) -> io::Result<i32> {
+        let mut code: i32 = 0;
+        let mut first: i32 = 0;
+        let mut index: usize = 0;
+
+        for len in 1..=MAX_CODE_LEN {
+            code |= reader.read_bit()? as i32;
+            let count = self.counts[len] as i32;
+            if code - first < count {
+                return Ok(self.symbols[index + (code - first) as usize]);
+            }
+            index += count as usize;
+            first += count;
+            first <<= 1;
+            code <<= 1;
+        }
+
+        Err(io::Error::new(io::ErrorKind::InvalidData, "Invalid delta Huffman code"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_delta_huffman_roundtrip_skewed() {
+        let mut deltas = vec![0i32; 200];
+        deltas.extend(vec![5i32; 30]);
+        deltas.extend(vec![-3000i32; 3]);
+
+        let table = DeltaHuffmanTable::build(&deltas);
+        let encoder = table.encoder();
+        let decoder = table.decoder();
+
+        let mut writer = BitWriter::new();
+        for &d in &deltas {
+            encoder.encode(d, &mut writer).unwrap();
+        }
+        let bytes = writer.finish();
+
+        let mut reader = BitReader::new(&bytes);
+        for &expected in &deltas {
+            assert_eq!(decoder.decode(&mut reader).unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn test_delta_huffman_single_symbol() {
+        let deltas = vec![7i32; 10];
+        let table = DeltaHuffmanTable::build(&deltas);
+        let encoder = table.encoder();
+        let decoder = table.decoder();
+
+        let mut writer = BitWriter::new();
+        for &d in &deltas {
+            encoder.encode(d, &mut writer).unwrap();
+        }
+        let bytes = writer.finish();
+
+        let mut reader = BitReader::new(&bytes);
+        for _ in 0..deltas.len() {
+            assert_eq!(decoder.decode(&mut reader).unwrap(), 7);
+        }
+    }
+
+    #[test]
+    fn test_delta_huffman_table_serialize_roundtrip() {
+        let deltas = vec![1i32, 1, 2, -5, -5, -5, 100];
+        let table = DeltaHuffmanTable::build(&deltas);
+        let bytes = table.serialize().unwrap();
+        let (restored, consumed) = DeltaHuffmanTable::deserialize(&bytes).unwrap();
+        assert_eq!(consumed, bytes.len());
+
+        let encoder = table.encoder();
+        let decoder = restored.decoder();
+        let mut writer = BitWriter::new();
+        for &d in &deltas {
+            encoder.encode(d, &mut writer).unwrap();
+        }
+        let packed = writer.finish();
+        let mut reader = BitReader::new(&packed);
+        for &expected in &deltas {
+            assert_eq!(decoder.decode(&mut reader).unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn test_delta_huffman_serialize_rejects_oversized_alphabet() {
+        // Built directly rather than via `build()` so the test exercises
+        // just the `u16`-count guard in `serialize`, not the (much slower)
+        // length-limiting merge over 65536+ distinct frequencies.
+        let n = u16::MAX as usize + 1;
+        let table = DeltaHuffmanTable {
+            symbols: (0..n as i32).collect(),
+            lengths: vec![1u8; n],
+        };
+        assert!(table.serialize().is_err());
+    }
+}
@@ -0,0 +1,230 @@
+use std::collections::HashMap;
+use std::io;
+
+const MAX_SYMBOLS: usize = 255;
+const MAX_SYMBOL_LEN: usize = 8;
+const ESCAPE: u8 = 255;
+
+/// An FSST-style static symbol table: up to 255 frequent byte substrings
+/// (each up to 8 bytes), trained once in bulk over a batch of strings and
+/// then used to replace matched substrings with a single code byte. Byte
+/// `255` is reserved as the escape code for literal bytes that don't match
+/// any trained symbol.
+#[derive(Debug, Clone, Default)]
+pub struct SymbolTable {
+    symbols: Vec<Vec<u8>>,
+    index: HashMap<Vec<u8>, u8>,
+}
+
+impl SymbolTable {
+    /// Greedily grows a shared symbol table over the concatenated corpus:
+    /// each round picks the uncovered substring with the highest total byte
+    /// savings (`occurrences * (len - 1)`), adds it as the next code, then
+    /// marks its non-overlapping occurrences as covered so later rounds
+    /// don't re-propose substrings already spoken for.
+    pub fn train_bulk(strings: &[&[u8]]) -> Self {
+        let owned: Vec<Vec<u8>> = strings.iter().map(|s| s.to_vec()).collect();
+        let mut covered: Vec<Vec<bool>> = owned.iter().map(|s| vec![false; s.len()]).collect();
+        let mut symbols: Vec<Vec<u8>> = Vec::new();
+
+        while symbols.len() < MAX_SYMBOLS {
+            let counts = Self::candidate_counts(&owned, &covered);
+            let best = counts
+                .into_iter()
+                .filter(|(sub, _)| sub.len() > 1)
+                .max_by_key(|(sub, count)| count * (sub.len() - 1));
+
+            match best {
+                Some((sub, count)) if count * (sub.len() - 1) > 0 => {
+                    Self::mark_covered(&owned, &mut covered, &sub);
+                    symbols.push(sub);
+                }
+                _ => break,
+            }
+        }
+
+        Self::from_symbols(symbols)
+    }
+
+    fn from_symbols(symbols: Vec<Vec<u8>>) -> Self {
+        let index = symbols
+            .iter()
+            .enumerate()
+            .map(|(code, sym)| (sym.clone(), code as u8))
+            .collect();
+        SymbolTable { symbols, index }
+    }
+
+    fn candidate_counts(strings: &[Vec<u8>], covered: &[Vec<bool>]) -> HashMap<Vec<u8>, usize> {
+        let mut counts: HashMap<Vec<u8>, usize> = HashMap::new();
+        for (s, cov) in strings.iter().zip(covered) {
+            let mut i = 0;
+            while i < s.len() {
+                if cov[i] {
+                    i += 1;
+                    continue;
+                }
+                let mut run = 0;
+                while i + run < s.len() && !cov[i + run] {
+                    run += 1;
+                }
+                for len in 1..=MAX_SYMBOL_LEN.min(run) {
+                    *counts.entry(s[i..i + len].to_vec()).or_insert(0) += 1;
+                }
+                i += 1;
+            }
+        }
+        counts
+    }
+
+    fn mark_covered(strings: &[Vec<u8>], covered: &mut [Vec<bool>], sub: &[u8]) {
+        for (s, cov) in strings.iter().zip(covered.iter_mut()) {
+            let mut i = 0;
+            while i + sub.len() <= s.len() {
+                if !cov[i..i + sub.len()].iter().any(|&c| c) && &s[i..i + sub.len()] == sub {
+                    for c in cov[i..i + sub.len()].iter_mut() {
+                        *c = true;
+                    }
+                    i += sub.len();
+                } else {
+                    i += 1;
+                }
+            }
+        }
+    }
+
+    /// Replaces matched substrings (greedy longest match) with their
+    /// single-byte code; bytes with no match are emitted as `ESCAPE` +
+    /// the literal byte.
+    pub fn compress_symbols(&self, text: &[u8]) -> Vec<u8> {
+        let mut out = Vec::new();
+        let mut i = 0;
+        while i < text.len() {
+            let max_len = MAX_SYMBOL_LEN.min(text.len() - i);
+            let matched = (1..=max_len)
+                .rev()
+                .find_map(|len| self.index.get(&text[i..i + len]).map(|&code| (code, len)));
+
+            match matched {
+                Some((code, len)) => {
+                    out.push(code);
+                    i += len;
+                }
+                None => {
+                    out.push(ESCAPE);
+                    out.push(text[i]);
+                    i += 1;
+                }
+            }
+        }
+        out
+    }
+
+    pub fn decompress_symbols(&self, data: &[u8]) -> io::Result<Vec<u8>> {
+        let mut out = Vec::new();
+        let mut i = 0;
+        while i < data.len() {
+            let code = data[i];
+            if code == ESCAPE {
+                i += 1;
+                if i >= data.len() {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        "Dangling FSST escape code",
+                    ));
+                }
+                out.push(data[i]);
+                i += 1;
+            } else {
+                let sym = self.symbols.get(code as usize).ok_or_else(|| {
+                    io::Error::new(io::ErrorKind::InvalidData, "Unknown FSST symbol code")
+                })?;
+                out.extend_from_slice(sym);
+                i += 1;
+            }
+        }
+        Ok(out)
+    }
+
+    /// Serializes the table as `[num_symbols: u8][len: u8][bytes...]*`.
+    pub fn serialize(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(1 + self.symbols.len() * 4);
+        out.push(self.symbols.len() as u8);
+        for sym in &self.symbols {
+            out.push(sym.len() as u8);
+            out.extend_from_slice(sym);
+        }
+        out
+    }
+
+    /// Reads a table written by [`Self::serialize`], returning it along
+    /// with the number of bytes consumed.
+    pub fn deserialize(data: &[u8]) -> io::Result<(Self, usize)> {
+        if data.is_empty() {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "Empty FSST table"));
+        }
+        let num_symbols = data[0] as usize;
+        let mut pos = 1;
+        let mut symbols = Vec::with_capacity(num_symbols);
+        for _ in 0..num_symbols {
+            if pos >= data.len() {
+                return Err(io::Error::new(io::ErrorKind::InvalidData, "Truncated FSST table"));
+            }
+            let len = data[pos] as usize;
+            pos += 1;
+            if pos + len > data.len() {
+                return Err(io::Error::new(io::ErrorKind::InvalidData, "Truncated FSST symbol"));
+            }
+            symbols.push(data[pos..pos + len].to_vec());
+            pos += len;
+        }
+        Ok((Self::from_symbols(symbols), pos))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_train_and_roundtrip() {
+        let strings: Vec<&[u8]> = vec![b"AAPL", b"GOOGL", b"AAPL", b"AMZN", b"AAPL"];
+        let table = SymbolTable::train_bulk(&strings);
+
+        for s in &strings {
+            let compressed = table.compress_symbols(s);
+            let decompressed = table.decompress_symbols(&compressed).unwrap();
+            assert_eq!(decompressed, s.to_vec());
+        }
+    }
+
+    #[test]
+    fn test_empty_corpus() {
+        let table = SymbolTable::train_bulk(&[]);
+        assert!(table.symbols.is_empty());
+        let compressed = table.compress_symbols(b"AAPL");
+        let decompressed = table.decompress_symbols(&compressed).unwrap();
+        assert_eq!(decompressed, b"AAPL");
+    }
+
+    #[test]
+    fn test_serialize_deserialize_roundtrip() {
+        let strings: Vec<&[u8]> = vec![b"AAPL", b"GOOGL", b"AAPL"];
+        let table = SymbolTable::train_bulk(&strings);
+        let blob = table.serialize();
+        let (restored, consumed) = SymbolTable::deserialize(&blob).unwrap();
+
+        assert_eq!(consumed, blob.len());
+        for s in &strings {
+            let compressed = table.compress_symbols(s);
+            let decompressed = restored.decompress_symbols(&compressed).unwrap();
+            assert_eq!(decompressed, s.to_vec());
+        }
+    }
+
+    #[test]
+    fn test_unknown_code_errors() {
+        let table = SymbolTable::default();
+        assert!(table.decompress_symbols(&[42]).is_err());
+    }
+}
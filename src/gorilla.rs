@@ -0,0 +1,143 @@
+use std::io;
+
+use crate::bitstream::{BitReader, BitWriter};
+
+/// Gorilla-style XOR float codec for one symbol's price series. Stores the
+/// IEEE-754 bits exactly: XORs the current value against the previous one,
+/// and if the meaningful (non-zero) bits fall inside the previous value's
+/// leading/trailing-zero window, only re-emits that window's bits rather
+/// than a fresh leading/length pair.
+pub struct GorillaCodec {
+    prev_bits: u64,
+    prev_leading: u32,
+    prev_trailing: u32,
+    has_window: bool,
+}
+
+fn mask(len: u32) -> u64 {
+    if len >= 64 {
+        u64::MAX
+    } else {
+        (1u64 << len) - 1
+    }
+}
+
+impl GorillaCodec {
+    pub fn new(initial: f64) -> Self {
+        GorillaCodec {
+            prev_bits: initial.to_bits(),
+            prev_leading: 0,
+            prev_trailing: 0,
+            has_window: false,
+        }
+    }
+
+    /// Encodes `value` against the running previous value. Emits a single
+    /// `0` bit when the value is unchanged.
+    pub fn encode(&mut self, writer: &mut BitWriter, value: f64) {
+        let bits = value.to_bits();
+        let xor = self.prev_bits ^ bits;
+
+        if xor == 0 {
+            writer.write_bits(0, 1);
+        } else {
+            writer.write_bits(1, 1);
+
+            let leading = xor.leading_zeros().min(31);
+            let trailing = xor.trailing_zeros();
+
+            if self.has_window && leading >= self.prev_leading && trailing >= self.prev_trailing {
+                writer.write_bits(0, 1);
+                let window_len = 64 - self.prev_leading - self.prev_trailing;
+                let meaningful = (xor >> self.prev_trailing) & mask(window_len);
+                writer.write_bits_u64(meaningful, window_len as u8);
+            } else {
+                writer.write_bits(1, 1);
+                let meaningful_len = 64 - leading - trailing;
+                writer.write_bits(leading, 5);
+                writer.write_bits(meaningful_len - 1, 6);
+                let meaningful = (xor >> trailing) & mask(meaningful_len);
+                writer.write_bits_u64(meaningful, meaningful_len as u8);
+                self.prev_leading = leading;
+                self.prev_trailing = trailing;
+                self.has_window = true;
+            }
+        }
+
+        self.prev_bits = bits;
+    }
+
+    pub fn decode(&mut self, reader: &mut BitReader) -> io::Result<f64> {
+        let changed = reader.read_bits(1)?;
+        if changed == 0 {
+            return Ok(f64::from_bits(self.prev_bits));
+        }
+
+        let control = reader.read_bits(1)?;
+        let xor = if control == 0 {
+            if !self.has_window {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "Gorilla stream referenced a window before one was established",
+                ));
+            }
+            let window_len = 64 - self.prev_leading - self.prev_trailing;
+            let meaningful = reader.read_bits_u64(window_len as u8)?;
+            meaningful << self.prev_trailing
+        } else {
+            let leading = reader.read_bits(5)?;
+            let meaningful_len = reader.read_bits(6)? + 1;
+            let trailing = 64 - leading - meaningful_len;
+            let meaningful = reader.read_bits_u64(meaningful_len as u8)?;
+            self.prev_leading = leading;
+            self.prev_trailing = trailing;
+            self.has_window = true;
+            meaningful << trailing
+        };
+
+        self.prev_bits ^= xor;
+        Ok(f64::from_bits(self.prev_bits))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn roundtrip(values: &[f64]) {
+        let mut writer = BitWriter::new();
+        let mut encoder = GorillaCodec::new(values[0]);
+        for &v in &values[1..] {
+            encoder.encode(&mut writer, v);
+        }
+        let bytes = writer.finish();
+
+        let mut reader = BitReader::new(&bytes);
+        let mut decoder = GorillaCodec::new(values[0]);
+        for &expected in &values[1..] {
+            let got = decoder.decode(&mut reader).unwrap();
+            assert_eq!(got.to_bits(), expected.to_bits());
+        }
+    }
+
+    #[test]
+    fn test_gorilla_unchanged_values() {
+        roundtrip(&[150.0, 150.0, 150.0, 150.0]);
+    }
+
+    #[test]
+    fn test_gorilla_small_moves_share_window() {
+        roundtrip(&[150.0, 150.01, 150.02, 150.0, 149.99]);
+    }
+
+    #[test]
+    fn test_gorilla_exact_bit_reproduction() {
+        // A value that would be lossy under basis-point quantization.
+        roundtrip(&[150.123456789, 150.1234567890123, 0.1 + 0.2]);
+    }
+
+    #[test]
+    fn test_gorilla_large_jump_then_small_moves() {
+        roundtrip(&[1.0, 1_000_000.5, 1_000_000.51, 1_000_000.52]);
+    }
+}
@@ -0,0 +1,227 @@
+use std::collections::HashMap;
+use std::error::Error;
+
+use crate::data::{read_verified_object, Commit, Snapshot};
+
+/// One step of a walked [`Commit`] chain: the content hash it's filed
+/// under in the object store plus the decoded commit itself.
+#[derive(Debug)]
+pub struct CommitEntry {
+    pub hash: String,
+    pub commit: Commit,
+}
+
+/// Per-ticker difference between the `Snapshot`s two commits reference, as
+/// reported by [`diff`].
+#[derive(Debug, PartialEq, Eq)]
+pub enum DiffEntry {
+    Added { ticker: String, blob_hash: String },
+    Removed { ticker: String, blob_hash: String },
+    Changed {
+        ticker: String,
+        old_blob_hash: String,
+        new_blob_hash: String,
+    },
+}
+
+/// Walks `head_hash` back through `parent_hash` links to the root commit,
+/// re-hashing each object against the hash it's stored under along the way
+/// (the same check [`verify_chain`] runs, just incidental here), and
+/// returns the chain oldest-first.
+pub fn log(head_hash: &str) -> Result<Vec<CommitEntry>, Box<dyn Error>> {
+    let mut chain = Vec::new();
+    let mut current = Some(head_hash.to_string());
+
+    while let Some(hash) = current {
+        let commit: Commit = read_verified_object(&hash)?;
+        current = commit.parent_hash.clone();
+        chain.push(CommitEntry { hash, commit });
+    }
+
+    chain.reverse();
+    Ok(chain)
+}
+
+/// Walks `head_hash` back to the root, confirming every object along the
+/// way still hashes to the content address it's filed under — catching
+/// both a tampered object (bytes changed, hash now mismatches) and a
+/// dangling link (`parent_hash` pointing at an object that no longer
+/// exists, surfaced as the underlying read error).
+pub fn verify_chain(head_hash: &str) -> Result<(), Box<dyn Error>> {
+    let mut current = Some(head_hash.to_string());
+
+    while let Some(hash) = current {
+        let commit: Commit = read_verified_object(&hash)?;
+        current = commit.parent_hash;
+    }
+
+    Ok(())
+}
+
+/// Compares the `Snapshot`s referenced by `commit_a` and `commit_b`,
+/// reporting each ticker added, removed, or changed to a different
+/// `blob_hash` between the two, sorted by ticker.
+pub fn diff(commit_a: &str, commit_b: &str) -> Result<Vec<DiffEntry>, Box<dyn Error>> {
+    let a: Commit = read_verified_object(commit_a)?;
+    let b: Commit = read_verified_object(commit_b)?;
+    let snapshot_a: Snapshot = read_verified_object(&a.tree_hash)?;
+    let snapshot_b: Snapshot = read_verified_object(&b.tree_hash)?;
+
+    let map_a: HashMap<&str, &str> = snapshot_a
+        .entries
+        .iter()
+        .map(|e| (e.ticker.as_str(), e.blob_hash.as_str()))
+        .collect();
+    let map_b: HashMap<&str, &str> = snapshot_b
+        .entries
+        .iter()
+        .map(|e| (e.ticker.as_str(), e.blob_hash.as_str()))
+        .collect();
+
+    let mut tickers: Vec<&str> = map_a.keys().chain(map_b.keys()).copied().collect();
+    tickers.sort_unstable();
+    tickers.dedup();
+
+    let mut entries = Vec::new();
+    for ticker in tickers {
+        match (map_a.get(ticker), map_b.get(ticker)) {
+            (None, Some(&new_hash)) => entries.push(DiffEntry::Added {
+                ticker: ticker.to_string(),
+                blob_hash: new_hash.to_string(),
+            }),
+            (Some(&old_hash), None) => entries.push(DiffEntry::Removed {
+                ticker: ticker.to_string(),
+                blob_hash: old_hash.to_string(),
+            }),
+            (Some(&old_hash), Some(&new_hash)) if old_hash != new_hash => {
+                entries.push(DiffEntry::Changed {
+                    ticker: ticker.to_string(),
+                    old_blob_hash: old_hash.to_string(),
+                    new_blob_hash: new_hash.to_string(),
+                })
+            }
+            _ => {}
+        }
+    }
+
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::{write_commit_object, write_snapshot_object, Entry};
+    use std::sync::Mutex;
+
+    // write_and_hash_object always writes under the repo-relative
+    // `.cndl/objects` dir, so these tests serialize on it and clean up
+    // after themselves to avoid cross-test interference.
+    static OBJECTS_DIR_LOCK: Mutex<()> = Mutex::new(());
+
+    fn snapshot_with(entries: &[(&str, &str)]) -> Snapshot {
+        Snapshot {
+            entries: entries
+                .iter()
+                .map(|(ticker, hash)| Entry {
+                    ticker: ticker.to_string(),
+                    blob_hash: hash.to_string(),
+                })
+                .collect(),
+        }
+    }
+
+    fn commit(tree_hash: &str, parent_hash: Option<&str>) -> Commit {
+        Commit {
+            tree_hash: tree_hash.to_string(),
+            merkle_root: [0u8; 32],
+            parent_hash: parent_hash.map(str::to_string),
+            timestamp: 0,
+            author: "test".to_string(),
+            message: "test commit".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_log_walks_chain_oldest_first() {
+        let _guard = OBJECTS_DIR_LOCK.lock().unwrap();
+
+        let snap_hash = write_snapshot_object(&snapshot_with(&[("AAPL", "a".repeat(64).as_str())])).unwrap();
+        let root_hash = write_commit_object(&commit(&snap_hash, None)).unwrap();
+        let child_hash = write_commit_object(&commit(&snap_hash, Some(&root_hash))).unwrap();
+
+        let chain = log(&child_hash).unwrap();
+        assert_eq!(chain.len(), 2);
+        assert_eq!(chain[0].hash, root_hash);
+        assert_eq!(chain[1].hash, child_hash);
+
+        std::fs::remove_dir_all(".cndl").ok();
+    }
+
+    #[test]
+    fn test_verify_chain_detects_tampered_object() {
+        let _guard = OBJECTS_DIR_LOCK.lock().unwrap();
+
+        let snap_hash = write_snapshot_object(&snapshot_with(&[("AAPL", "a".repeat(64).as_str())])).unwrap();
+        let root_hash = write_commit_object(&commit(&snap_hash, None)).unwrap();
+        let child_hash = write_commit_object(&commit(&snap_hash, Some(&root_hash))).unwrap();
+
+        assert!(verify_chain(&child_hash).is_ok());
+
+        let (prefix, fname) = root_hash.split_at(2);
+        let path = std::path::Path::new(".cndl/objects").join(prefix).join(fname);
+        let mut bytes = std::fs::read(&path).unwrap();
+        bytes[0] ^= 0xFF;
+        std::fs::write(&path, &bytes).unwrap();
+
+        assert!(verify_chain(&child_hash).is_err());
+
+        std::fs::remove_dir_all(".cndl").ok();
+    }
+
+    #[test]
+    fn test_diff_reports_added_removed_changed() {
+        let _guard = OBJECTS_DIR_LOCK.lock().unwrap();
+
+        let snap_a = write_snapshot_object(&snapshot_with(&[
+            ("AAPL", "a".repeat(64).as_str()),
+            ("GOOGL", "b".repeat(64).as_str()),
+        ]))
+        .unwrap();
+        let snap_b = write_snapshot_object(&snapshot_with(&[
+            ("AAPL", "c".repeat(64).as_str()),
+            ("MSFT", "d".repeat(64).as_str()),
+        ]))
+        .unwrap();
+
+        let commit_a = write_commit_object(&commit(&snap_a, None)).unwrap();
+        let commit_b = write_commit_object(&commit(&snap_b, Some(&commit_a))).unwrap();
+
+        let mut entries = diff(&commit_a, &commit_b).unwrap();
+        entries.sort_by_key(|e| match e {
+            DiffEntry::Added { ticker, .. } => ticker.clone(),
+            DiffEntry::Removed { ticker, .. } => ticker.clone(),
+            DiffEntry::Changed { ticker, .. } => ticker.clone(),
+        });
+
+        assert_eq!(
+            entries,
+            vec![
+                DiffEntry::Changed {
+                    ticker: "AAPL".to_string(),
+                    old_blob_hash: "a".repeat(64),
+                    new_blob_hash: "c".repeat(64),
+                },
+                DiffEntry::Removed {
+                    ticker: "GOOGL".to_string(),
+                    blob_hash: "b".repeat(64),
+                },
+                DiffEntry::Added {
+                    ticker: "MSFT".to_string(),
+                    blob_hash: "d".repeat(64),
+                },
+            ]
+        );
+
+        std::fs::remove_dir_all(".cndl").ok();
+    }
+}
@@ -0,0 +1,257 @@
+use std::io;
+
+use crate::bitstream::{BitReader, BitWriter};
+use crate::codec::Codec;
+
+const MAX_CODE_LEN: usize = 15;
+
+/// Canonical Huffman entropy codec, modeled on the classic inflate/deflate
+/// dynamic-table scheme: code lengths are length-limited to
+/// [`MAX_CODE_LEN`] bits, canonical codes are derived by sorting symbols by
+/// `(code_length, symbol)`, and only the per-symbol length table needs to be
+/// stored — the codes themselves are reconstructed from it on decode.
+pub struct Huffman;
+
+impl Codec for Huffman {
+    fn compress(&self, raw: &[u8]) -> io::Result<Vec<u8>> {
+        if raw.is_empty() {
+            return Ok(vec![0, 0, 0, 0]);
+        }
+
+        let lengths = build_code_lengths(raw);
+        let table = CanonicalTable::from_lengths(&lengths)?;
+
+        let mut out = Vec::new();
+        out.extend_from_slice(&(raw.len() as u32).to_le_bytes());
+
+        let rle = rle_encode_lengths(&lengths);
+        out.extend_from_slice(&(rle.len() as u32).to_le_bytes());
+        out.extend_from_slice(&rle);
+
+        let mut writer = BitWriter::new();
+        for &byte in raw {
+            let (code, len) = table.encode(byte);
+            writer.write_bits(code, len);
+        }
+        out.extend_from_slice(&writer.finish());
+
+        Ok(out)
+    }
+
+    fn decompress(&self, data: &[u8]) -> io::Result<Vec<u8>> {
+        if data.len() < 4 {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "Huffman stream too short"));
+        }
+        let raw_len = u32::from_le_bytes(data[0..4].try_into().unwrap()) as usize;
+        if raw_len == 0 {
+            return Ok(Vec::new());
+        }
+
+        if data.len() < 8 {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "Huffman stream too short"));
+        }
+        let rle_len = u32::from_le_bytes(data[4..8].try_into().unwrap()) as usize;
+        let rle_start: usize = 8;
+        let rle_end = rle_start
+            .checked_add(rle_len)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "Huffman length overflow"))?;
+        if rle_end > data.len() {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "Huffman stream truncated"));
+        }
+
+        let lengths = rle_decode_lengths(&data[rle_start..rle_end])?;
+        let decoder = CanonicalDecoder::from_lengths(&lengths)?;
+
+        let mut reader = BitReader::new(&data[rle_end..]);
+        // `raw_len` is an attacker-controlled header field; a canonical
+        // code can be as short as 1 bit, so cap the upfront allocation at
+        // the number of bits actually available rather than rejecting large
+        // values outright — anything beyond that runs out of bits and
+        // errors out of `decoder.decode` before it matters.
+        let max_symbols = (data.len() - rle_end).saturating_mul(8);
+        let mut out = Vec::with_capacity(raw_len.min(max_symbols));
+        for _ in 0..raw_len {
+            out.push(decoder.decode(&mut reader)?);
+        }
+        Ok(out)
+    }
+
+    fn id(&self) -> u8 {
+        1
+    }
+}
+
+/// Counts byte frequencies and derives length-limited canonical code
+/// lengths via [`crate::huffman_core::build_code_lengths`]'s length-limited
+/// merge, reducing the fixed 256-byte alphabet down to the bytes that
+/// actually occur and scattering the resulting lengths back by byte value.
+fn build_code_lengths(raw: &[u8]) -> [u8; 256] {
+    let mut freq = [0u64; 256];
+    for &b in raw {
+        freq[b as usize] += 1;
+    }
+
+    let present: Vec<usize> = (0..256).filter(|&i| freq[i] > 0).collect();
+    let dense_freq: Vec<u64> = present.iter().map(|&i| freq[i]).collect();
+    let dense_lengths = crate::huffman_core::build_code_lengths(&dense_freq, MAX_CODE_LEN);
+
+    let mut lengths = [0u8; 256];
+    for (&sym, &len) in present.iter().zip(dense_lengths.iter()) {
+        lengths[sym] = len;
+    }
+    lengths
+}
+
+/// A canonical Huffman table: per-symbol `(code, length)` built by sorting
+/// symbols by `(code_length, symbol)` and assigning consecutive codes
+/// within each length.
+struct CanonicalTable {
+    codes: [(u32, u8); 256],
+}
+
+impl CanonicalTable {
+    fn from_lengths(lengths: &[u8; 256]) -> io::Result<Self> {
+        let mut order: Vec<usize> = (0..256).filter(|&i| lengths[i] > 0).collect();
+        order.sort_by_key(|&i| (lengths[i], i as u8));
+
+        let mut codes = [(0u32, 0u8); 256];
+        let mut code = 0u32;
+        let mut prev_len = 0u8;
+        for sym in order {
+            let len = lengths[sym];
+            code <<= len - prev_len;
+            codes[sym] = (code, len);
+            code += 1;
+            prev_len = len;
+        }
+        Ok(CanonicalTable { codes })
+    }
+
+    fn encode(&self, symbol: u8) -> (u32, u8) {
+        self.codes[symbol as usize]
+    }
+}
+
+/// The inflate-style decode tables: `counts[len]` holds how many codes have
+/// that length, `symbols[]` holds symbols ordered by `(length, symbol)`.
+struct CanonicalDecoder {
+    counts: [u16; MAX_CODE_LEN + 1],
+    symbols: Vec<u8>,
+}
+
+impl CanonicalDecoder {
+    fn from_lengths(lengths: &[u8; 256]) -> io::Result<Self> {
+        let mut counts = [0u16; MAX_CODE_LEN + 1];
+        for &len in lengths.iter() {
+            if len > 0 {
+                counts[len as usize] += 1;
+            }
+        }
+
+        let mut order: Vec<usize> = (0..256).filter(|&i| lengths[i] > 0).collect();
+        order.sort_by_key(|&i| (lengths[i], i as u8));
+        let symbols = order.into_iter().map(|i| i as u8).collect();
+
+        Ok(CanonicalDecoder { counts, symbols })
+    }
+
+    fn decode(&self, reader: &mut BitReader) -> io::Result<u8> {
+        let mut code: i32 = 0;
+        let mut first: i32 = 0;
+        let mut index: usize = 0;
+
+        for len in 1..=MAX_CODE_LEN {
+            code |= reader.read_bit()? as i32;
+            let count = self.counts[len] as i32;
+            if code - first < count {
+                return Ok(self.symbols[index + (code - first) as usize]);
+            }
+            index += count as usize;
+            first += count;
+            first <<= 1;
+            code <<= 1;
+        }
+
+        Err(io::Error::new(io::ErrorKind::InvalidData, "Invalid Huffman code"))
+    }
+}
+
+/// RLE-encodes a 256-entry code-length table as `(length, run)` byte pairs;
+/// runs longer than 255 are split across multiple pairs.
+fn rle_encode_lengths(lengths: &[u8; 256]) -> Vec<u8> {
+    let mut out = Vec::new();
+    let mut i = 0;
+    while i < lengths.len() {
+        let value = lengths[i];
+        let mut run = 1usize;
+        while i + run < lengths.len() && lengths[i + run] == value && run < 255 {
+            run += 1;
+        }
+        out.push(value);
+        out.push(run as u8);
+        i += run;
+    }
+    out
+}
+
+fn rle_decode_lengths(rle: &[u8]) -> io::Result<[u8; 256]> {
+    let mut lengths = [0u8; 256];
+    let mut pos = 0;
+    let mut idx = 0;
+    while pos + 1 < rle.len() + 1 && idx < 256 {
+        if pos + 2 > rle.len() {
+            break;
+        }
+        let value = rle[pos];
+        let run = rle[pos + 1] as usize;
+        for _ in 0..run {
+            if idx >= 256 {
+                return Err(io::Error::new(io::ErrorKind::InvalidData, "Huffman length table overflow"));
+            }
+            lengths[idx] = value;
+            idx += 1;
+        }
+        pos += 2;
+    }
+    Ok(lengths)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_huffman_roundtrip_skewed() {
+        let mut raw = vec![0u8; 1000];
+        raw.extend(vec![1u8; 50]);
+        raw.push(255u8);
+
+        let huffman = Huffman;
+        let compressed = huffman.compress(&raw).unwrap();
+        let decompressed = huffman.decompress(&compressed).unwrap();
+        assert_eq!(raw, decompressed);
+        assert!(compressed.len() < raw.len());
+    }
+
+    #[test]
+    fn test_huffman_roundtrip_all_symbols() {
+        let raw: Vec<u8> = (0..=255u8).cycle().take(2048).collect();
+        let huffman = Huffman;
+        let compressed = huffman.compress(&raw).unwrap();
+        let decompressed = huffman.decompress(&compressed).unwrap();
+        assert_eq!(raw, decompressed);
+    }
+
+    #[test]
+    fn test_huffman_empty() {
+        let huffman = Huffman;
+        let compressed = huffman.compress(&[]).unwrap();
+        let decompressed = huffman.decompress(&compressed).unwrap();
+        assert!(decompressed.is_empty());
+    }
+
+    #[test]
+    fn test_huffman_id() {
+        assert_eq!(Huffman.id(), 1);
+    }
+}
@@ -0,0 +1,113 @@
+//! Length-limited canonical Huffman code-length construction shared by
+//! [`crate::huffman`] (fixed 256-byte alphabet) and [`crate::delta_huffman`]
+//! (arbitrary `i32` delta alphabet). Both callers reduce their alphabet to a
+//! dense `freq` slice — one nonzero entry per live symbol, in a stable
+//! order they each keep track of separately — and get back code lengths in
+//! that same order.
+
+/// Derives length-limited canonical code lengths for `freq` via the classic
+/// in-place merge used by JPEG's optimal Huffman table builder: repeatedly
+/// combine the two least-frequent symbols, tracking depth through a linked
+/// "parent" array, then fold any resulting lengths over `max_len` back down
+/// while preserving the Kraft inequality.
+pub(crate) fn build_code_lengths(freq: &[u64], max_len: usize) -> Vec<u8> {
+    let n = freq.len();
+    if n == 0 {
+        return Vec::new();
+    }
+
+    // A dummy (n+1)-th symbol with freq 1 guarantees at least two non-zero
+    // symbols (so the merge loop always terminates cleanly) and reserves
+    // one slot at the deepest level that we discard at the end.
+    let mut work = vec![0u64; n + 1];
+    work[..n].copy_from_slice(freq);
+    work[n] = 1;
+
+    let mut codesize = vec![0u32; n + 1];
+    let mut others = vec![-1i32; n + 1];
+
+    loop {
+        let v1 = min_nonzero(&work, None);
+        let v2 = min_nonzero(&work, v1);
+        let (v1, v2) = match (v1, v2) {
+            (Some(v1), Some(v2)) => (v1, v2),
+            _ => break,
+        };
+
+        work[v1] += work[v2];
+        work[v2] = 0;
+
+        codesize[v1] += 1;
+        let mut k = v1;
+        while others[k] >= 0 {
+            k = others[k] as usize;
+            codesize[k] += 1;
+        }
+        others[k] = v2 as i32;
+
+        codesize[v2] += 1;
+        let mut k = v2;
+        while others[k] >= 0 {
+            k = others[k] as usize;
+            codesize[k] += 1;
+        }
+    }
+
+    let mut bits = vec![0u32; max_len + 2];
+    for &size in codesize.iter() {
+        if size > 0 {
+            let len = (size as usize).min(max_len + 1);
+            bits[len] += 1;
+        }
+    }
+
+    // Redistribute any lengths beyond max_len.
+    for i in (max_len + 1..bits.len()).rev() {
+        while bits[i] > 0 {
+            let mut j = i - 2;
+            while bits[j] == 0 {
+                j -= 1;
+            }
+            bits[i] -= 2;
+            bits[i - 1] += 1;
+            bits[j + 1] += 2;
+            bits[j] -= 1;
+        }
+    }
+    // Drop the slot reserved by the dummy symbol.
+    for i in (1..=max_len).rev() {
+        if bits[i] > 0 {
+            bits[i] -= 1;
+            break;
+        }
+    }
+
+    let mut order: Vec<usize> = (0..n).collect();
+    order.sort_by(|&a, &b| freq[b].cmp(&freq[a]));
+
+    let mut lengths = vec![0u8; n];
+    let mut iter = order.into_iter();
+    for (len, &count) in bits.iter().enumerate().skip(1).take(max_len) {
+        for _ in 0..count {
+            if let Some(sym) = iter.next() {
+                lengths[sym] = len as u8;
+            }
+        }
+    }
+    lengths
+}
+
+fn min_nonzero(freq: &[u64], exclude: Option<usize>) -> Option<usize> {
+    let mut best: Option<usize> = None;
+    for (i, &f) in freq.iter().enumerate() {
+        if f == 0 || Some(i) == exclude {
+            continue;
+        }
+        match best {
+            None => best = Some(i),
+            Some(b) if f < freq[b] => best = Some(i),
+            _ => {}
+        }
+    }
+    best
+}
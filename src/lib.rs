@@ -1,7 +1,30 @@
+mod bitstream;
+mod block;
+mod codec;
 mod crc32;
+mod data;
 mod delta_encoding;
+mod delta_huffman;
+mod fsst;
+mod gorilla;
+mod history;
+mod huffman;
+mod huffman_core;
+mod lz4;
+mod merkle;
+mod stream;
 mod types;
+mod varint;
+mod zerocopy;
 mod compression;
 
+pub use block::{BlockIndexEntry, BlockReader, BlockWriter};
+pub use codec::{Codec, Store};
+pub use data::{write_blob_object, write_commit_object, write_snapshot_object, Blob, Commit, Entry, Snapshot};
+pub use history::{diff, log, verify_chain, CommitEntry, DiffEntry};
+pub use huffman::Huffman;
+pub use lz4::Lz4;
+pub use merkle::{merkle_proof, merkle_root, verify_proof, Side};
+pub use stream::TickDecoder;
 pub use types::{Price, Tick, CompressionMetadata};
-pub use compression::CompressedTimeSeries;
+pub use compression::{CompressedTimeSeries, CompressedTimeSeriesBuilder};
@@ -119,7 +119,12 @@ fn cmd_info(input: &str) -> Result<(), Box<dyn std::error::Error>> {
     let meta = compressed.metadata();
 
     println!("File: {}", input);
-    println!("Version: {}", meta.version);
+    let packing = if meta.version >= 3 { "bit-packed" } else { "byte-aligned" };
+    println!("Version: {} ({} deltas)", meta.version, packing);
+    println!(
+        "Precision: {}",
+        if meta.lossless { "lossless (Gorilla XOR)" } else { "lossy (basis-point)" }
+    );
     println!(
         "Symbols: {} ({})",
         meta.num_symbols,
@@ -127,6 +132,7 @@ fn cmd_info(input: &str) -> Result<(), Box<dyn std::error::Error>> {
     );
     println!("Ticks: {}", meta.num_ticks);
     println!("Compressed size: {} bytes", meta.compressed_size);
+    println!("Uncompressed size: {} bytes", meta.uncompressed_size);
     println!("Base timestamp: {}", meta.base_timestamp);
     println!();
     println!("Checksums:");
@@ -0,0 +1,223 @@
+use std::io;
+
+use sha2::{Digest, Sha256};
+
+use crate::data::{Entry, Snapshot};
+
+/// Which side of the current node a proof step's sibling hash sits on,
+/// needed because `SHA256(left ‖ right)` is order-sensitive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    Left,
+    Right,
+}
+
+fn hash_pair(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(left);
+    hasher.update(right);
+    hasher.finalize().into()
+}
+
+/// Decodes a lowercase-hex SHA-256 digest, the format `write_and_hash_object`
+/// stores in [`Entry::blob_hash`], back into raw bytes.
+fn decode_hex32(hex_str: &str) -> io::Result<[u8; 32]> {
+    if hex_str.len() != 64 || !hex_str.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "blob_hash must be 64 hex characters",
+        ));
+    }
+
+    let mut out = [0u8; 32];
+    for (i, byte) in out.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&hex_str[i * 2..i * 2 + 2], 16)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    }
+    Ok(out)
+}
+
+/// Entries sorted by ticker with their `blob_hash` decoded to raw bytes —
+/// the leaf order every Merkle operation below must agree on.
+fn sorted_leaves(snapshot: &Snapshot) -> io::Result<Vec<(&Entry, [u8; 32])>> {
+    let mut entries: Vec<&Entry> = snapshot.entries.iter().collect();
+    entries.sort_by(|a, b| a.ticker.cmp(&b.ticker));
+
+    entries
+        .into_iter()
+        .map(|e| decode_hex32(&e.blob_hash).map(|h| (e, h)))
+        .collect()
+}
+
+/// One level up: pairs adjacent nodes with `SHA256(left ‖ right)`,
+/// duplicating the last node (Bitcoin-style) when the level has an odd
+/// count so every level pairs evenly.
+fn next_level(level: &[[u8; 32]]) -> Vec<[u8; 32]> {
+    let mut next = Vec::with_capacity(level.len().div_ceil(2));
+    let mut i = 0;
+    while i < level.len() {
+        let left = &level[i];
+        let right = level.get(i + 1).unwrap_or(left);
+        next.push(hash_pair(left, right));
+        i += 2;
+    }
+    next
+}
+
+fn root_from_leaves(leaves: &[[u8; 32]]) -> [u8; 32] {
+    let mut level = leaves.to_vec();
+    while level.len() > 1 {
+        level = next_level(&level);
+    }
+    level[0]
+}
+
+/// Builds the sibling path for `leaf_index`, walking up the same levels
+/// [`root_from_leaves`] would, but recording the other half of each pair
+/// instead of hashing it away.
+fn build_proof(leaves: &[[u8; 32]], leaf_index: usize) -> Vec<(Side, [u8; 32])> {
+    let mut proof = Vec::new();
+    let mut level = leaves.to_vec();
+    let mut idx = leaf_index;
+
+    while level.len() > 1 {
+        let pair_idx = idx ^ 1;
+        let sibling = *level.get(pair_idx).unwrap_or(&level[idx]);
+        let side = if idx.is_multiple_of(2) {
+            Side::Right
+        } else {
+            Side::Left
+        };
+        proof.push((side, sibling));
+
+        level = next_level(&level);
+        idx /= 2;
+    }
+
+    proof
+}
+
+/// Computes the Merkle root over a [`Snapshot`]'s entry `blob_hash`es, so a
+/// [`crate::data::Commit`] can carry this single 32-byte root instead of the
+/// whole entry list.
+pub fn merkle_root(snapshot: &Snapshot) -> io::Result<[u8; 32]> {
+    let leaves: Vec<[u8; 32]> = sorted_leaves(snapshot)?
+        .into_iter()
+        .map(|(_, h)| h)
+        .collect();
+
+    if leaves.is_empty() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "Cannot build a Merkle root over an empty snapshot",
+        ));
+    }
+
+    Ok(root_from_leaves(&leaves))
+}
+
+/// Returns the sibling path proving `ticker`'s blob belongs to `snapshot`,
+/// for [`verify_proof`] to fold back to the snapshot's [`merkle_root`]
+/// without the verifier needing every other entry.
+pub fn merkle_proof(snapshot: &Snapshot, ticker: &str) -> io::Result<Vec<(Side, [u8; 32])>> {
+    let leaves = sorted_leaves(snapshot)?;
+    let idx = leaves
+        .iter()
+        .position(|(e, _)| e.ticker == ticker)
+        .ok_or_else(|| {
+            io::Error::new(io::ErrorKind::NotFound, format!("Unknown ticker {ticker}"))
+        })?;
+
+    let hashes: Vec<[u8; 32]> = leaves.into_iter().map(|(_, h)| h).collect();
+    Ok(build_proof(&hashes, idx))
+}
+
+/// Folds `leaf` up through `proof`'s sibling path and checks the result
+/// against `root`. Lets a light client validate one ticker's blob against a
+/// commit's Merkle root in `O(log n)` without downloading the whole
+/// snapshot.
+pub fn verify_proof(leaf: [u8; 32], proof: &[(Side, [u8; 32])], root: [u8; 32]) -> bool {
+    let mut acc = leaf;
+    for (side, sibling) in proof {
+        acc = match side {
+            Side::Left => hash_pair(sibling, &acc),
+            Side::Right => hash_pair(&acc, sibling),
+        };
+    }
+    acc == root
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::Entry;
+
+    fn make_snapshot(tickers: &[&str]) -> Snapshot {
+        let entries = tickers
+            .iter()
+            .map(|t| Entry {
+                ticker: t.to_string(),
+                blob_hash: format!("{:x}", Sha256::digest(t.as_bytes())),
+            })
+            .collect();
+        Snapshot { entries }
+    }
+
+    #[test]
+    fn test_root_is_deterministic_regardless_of_entry_order() {
+        let a = make_snapshot(&["AAPL", "GOOGL", "MSFT"]);
+        let b = make_snapshot(&["MSFT", "AAPL", "GOOGL"]);
+        assert_eq!(merkle_root(&a).unwrap(), merkle_root(&b).unwrap());
+    }
+
+    #[test]
+    fn test_proof_verifies_against_root() {
+        let snapshot = make_snapshot(&["AAPL", "GOOGL", "MSFT", "TSLA", "AMZN"]);
+        let root = merkle_root(&snapshot).unwrap();
+
+        for entry in &snapshot.entries {
+            let leaf = decode_hex32(&entry.blob_hash).unwrap();
+            let proof = merkle_proof(&snapshot, &entry.ticker).unwrap();
+            assert!(verify_proof(leaf, &proof, root));
+        }
+    }
+
+    #[test]
+    fn test_proof_fails_for_wrong_leaf() {
+        let snapshot = make_snapshot(&["AAPL", "GOOGL", "MSFT"]);
+        let root = merkle_root(&snapshot).unwrap();
+        let proof = merkle_proof(&snapshot, "AAPL").unwrap();
+
+        let wrong_leaf = decode_hex32(&"ff".repeat(32)).unwrap();
+        assert!(!verify_proof(wrong_leaf, &proof, root));
+    }
+
+    #[test]
+    fn test_odd_leaf_count_duplicates_last_node() {
+        let snapshot = make_snapshot(&["AAPL", "GOOGL", "MSFT"]);
+        let root = merkle_root(&snapshot).unwrap();
+
+        let leaves: Vec<[u8; 32]> = sorted_leaves(&snapshot)
+            .unwrap()
+            .into_iter()
+            .map(|(_, h)| h)
+            .collect();
+        let expected = hash_pair(
+            &hash_pair(&leaves[0], &leaves[1]),
+            &hash_pair(&leaves[2], &leaves[2]),
+        );
+        assert_eq!(root, expected);
+    }
+
+    #[test]
+    fn test_unknown_ticker_errors() {
+        let snapshot = make_snapshot(&["AAPL", "GOOGL"]);
+        assert!(merkle_proof(&snapshot, "NVDA").is_err());
+    }
+
+    #[test]
+    fn test_empty_snapshot_errors() {
+        let snapshot = make_snapshot(&[]);
+        assert!(merkle_root(&snapshot).is_err());
+    }
+}
@@ -0,0 +1,233 @@
+use std::collections::HashMap;
+use std::io;
+
+use crate::bitstream::BitReader;
+use crate::delta_encoding::DeltaEncoding;
+use crate::delta_huffman::{DeltaHuffmanDecoder, DeltaHuffmanTable};
+use crate::gorilla::GorillaCodec;
+use crate::types::Tick;
+use crate::varint;
+
+/// Which per-delta bit encoding the pushed byte stream uses, mirroring the
+/// codecs [`crate::CompressedTimeSeries::compress_with`] can choose between.
+/// `Huffman` starts in `PendingHuffmanTable` because its canonical table is itself
+/// stored at the front of the stream and can only be parsed once enough
+/// bytes have been pushed.
+enum DeltaMode {
+    Bitpacked,
+    PendingHuffmanTable,
+    Huffman(DeltaHuffmanDecoder),
+    Gorilla(Vec<GorillaCodec>),
+}
+
+/// Incremental tick decoder, modeled on inflate's chunked
+/// `decompress_data(src, out, repeat)` loop: callers `push` bytes as they
+/// arrive (e.g. 512-byte reads off a socket or file) and drain completed
+/// ticks with `next_tick`, without ever buffering the whole delta stream.
+///
+/// Operates on the *decoded* delta byte stream (the same bytes
+/// `CompressedTimeSeries::decompress` walks internally) — if the series was
+/// stored with a block-oriented [`crate::Codec`] such as `Huffman`, run that
+/// codec's `decompress` first and feed its output here.
+pub struct TickDecoder {
+    symbols: Vec<String>,
+    base_ts: u64,
+    curr: Vec<f64>,
+    buf: Vec<u8>,
+    pending_first: bool,
+    mode: DeltaMode,
+    prev_ts_delta: u32,
+    prev_gap: i64,
+}
+
+impl TickDecoder {
+    pub fn new(symbols: Vec<String>, ref_frame: Vec<f64>, base_ts: u64) -> Self {
+        Self::with_delta_codec(symbols, ref_frame, base_ts, false, 0)
+    }
+
+    /// Like [`Self::new`], but lets the caller specify the delta codec the
+    /// stream was compressed with (`lossless` / `delta_codec`, mirroring
+    /// [`crate::CompressionMetadata`]) instead of assuming the default
+    /// bit-packed [`DeltaEncoding`] scheme.
+    pub fn with_delta_codec(
+        symbols: Vec<String>,
+        ref_frame: Vec<f64>,
+        base_ts: u64,
+        lossless: bool,
+        delta_codec: u8,
+    ) -> Self {
+        let mode = if lossless {
+            DeltaMode::Gorilla(ref_frame.iter().map(|&v| GorillaCodec::new(v)).collect())
+        } else if delta_codec == 1 {
+            DeltaMode::PendingHuffmanTable
+        } else {
+            DeltaMode::Bitpacked
+        };
+
+        TickDecoder {
+            symbols,
+            base_ts,
+            curr: ref_frame,
+            buf: Vec::new(),
+            pending_first: true,
+            mode,
+            prev_ts_delta: 0,
+            prev_gap: 0,
+        }
+    }
+
+    /// Appends another chunk of the delta byte stream. Cheap; just grows the
+    /// internal reassembly buffer.
+    pub fn push(&mut self, src: &[u8]) -> io::Result<()> {
+        self.buf.extend_from_slice(src);
+        Ok(())
+    }
+
+    /// Returns the next fully-buffered tick, or `None` if more input is
+    /// needed before a complete tick can be decoded.
+    pub fn next_tick(&mut self) -> io::Result<Option<Tick>> {
+        if self.pending_first {
+            self.pending_first = false;
+            return Ok(Some(self.make_tick(self.base_ts)));
+        }
+
+        if matches!(self.mode, DeltaMode::PendingHuffmanTable) {
+            let (table_len, table_len_len) = match varint::decode_u64(&self.buf, 0) {
+                Ok(v) => v,
+                Err(_) => return Ok(None),
+            };
+            let table_len = table_len as usize;
+            if table_len > self.buf.len().saturating_sub(table_len_len) {
+                return Ok(None);
+            }
+            let (table, _) =
+                DeltaHuffmanTable::deserialize(&self.buf[table_len_len..table_len_len + table_len])?;
+            self.buf.drain(0..table_len_len + table_len);
+            self.mode = DeltaMode::Huffman(table.decoder());
+        }
+
+        let n = self.symbols.len();
+        let bm_bytes = n.div_ceil(8);
+
+        let (dd_zigzag, dd_len) = match varint::decode_u64(&self.buf, 0) {
+            Ok(v) => v,
+            Err(_) => return Ok(None),
+        };
+
+        let bm_start = dd_len;
+        if self.buf.len() < bm_start + bm_bytes {
+            return Ok(None);
+        }
+        let bm = self.buf[bm_start..bm_start + bm_bytes].to_vec();
+
+        let packed_len_pos = bm_start + bm_bytes;
+        let (packed_len, packed_len_len) = match varint::decode_u64(&self.buf, packed_len_pos) {
+            Ok(v) => v,
+            Err(_) => return Ok(None),
+        };
+        let packed_len = packed_len as usize;
+
+        let payload_start = packed_len_pos + packed_len_len;
+        if packed_len > self.buf.len().saturating_sub(payload_start) {
+            return Ok(None);
+        }
+        let total_len = payload_start + packed_len;
+
+        let mut reader = BitReader::new(&self.buf[payload_start..total_len]);
+        for idx in 0..n {
+            if bm[idx / 8] & (1 << (idx % 8)) != 0 {
+                match &mut self.mode {
+                    DeltaMode::Gorilla(states) => {
+                        self.curr[idx] = states[idx].decode(&mut reader)?;
+                    }
+                    DeltaMode::Huffman(decoder) => {
+                        let delta_bp = decoder.decode(&mut reader)?;
+                        self.curr[idx] *= 1.0 + delta_bp as f64 / 10000.0;
+                    }
+                    DeltaMode::Bitpacked => {
+                        let delta_bp = DeltaEncoding::decode(&mut reader)?.to_basis();
+                        self.curr[idx] *= 1.0 + delta_bp as f64 / 10000.0;
+                    }
+                    DeltaMode::PendingHuffmanTable => unreachable!("table parsed above"),
+                }
+            }
+        }
+
+        let gap = self.prev_gap + varint::zigzag_decode(dd_zigzag);
+        let ts_delta = (self.prev_ts_delta as i64 + gap) as u32;
+        self.prev_gap = gap;
+        self.prev_ts_delta = ts_delta;
+
+        let ts = self.base_ts + ts_delta as u64;
+        self.buf.drain(0..total_len);
+        Ok(Some(self.make_tick(ts)))
+    }
+
+    fn make_tick(&self, timestamp: u64) -> Tick {
+        let mut prices = HashMap::new();
+        for (i, sym) in self.symbols.iter().enumerate() {
+            prices.insert(sym.clone(), self.curr[i]);
+        }
+        Tick { timestamp, prices }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::codec::{Codec, Store};
+    use crate::CompressedTimeSeries;
+
+    fn make_ticks() -> Vec<Tick> {
+        vec![
+            Tick {
+                timestamp: 1000,
+                prices: [("AAPL", 150.0), ("GOOGL", 2800.0)]
+                    .iter()
+                    .map(|(k, v)| (k.to_string(), *v))
+                    .collect(),
+            },
+            Tick {
+                timestamp: 1001,
+                prices: [("AAPL", 150.5), ("GOOGL", 2805.0)]
+                    .iter()
+                    .map(|(k, v)| (k.to_string(), *v))
+                    .collect(),
+            },
+            Tick {
+                timestamp: 1002,
+                prices: [("AAPL", 150.3), ("GOOGL", 2803.0)]
+                    .iter()
+                    .map(|(k, v)| (k.to_string(), *v))
+                    .collect(),
+            },
+        ]
+    }
+
+    #[test]
+    fn test_streaming_decode_matches_bulk() {
+        let ticks = make_ticks();
+        let compressed = CompressedTimeSeries::compress(&ticks).unwrap();
+        let expected = compressed.decompress().unwrap();
+
+        let raw = Store.decompress(compressed.data()).unwrap();
+        let mut decoder = compressed.tick_decoder();
+
+        // Feed the stream in small, arbitrarily-sized chunks.
+        let mut pushed = Vec::new();
+        for chunk in raw.chunks(3) {
+            decoder.push(chunk).unwrap();
+            pushed.extend_from_slice(chunk);
+        }
+
+        let mut got = Vec::new();
+        while let Some(tick) = decoder.next_tick().unwrap() {
+            got.push(tick);
+        }
+
+        assert_eq!(got.len(), expected.len());
+        for (g, e) in got.iter().zip(expected.iter()) {
+            assert_eq!(g.timestamp, e.timestamp);
+        }
+    }
+}
@@ -15,11 +15,15 @@ pub struct Tick {
 #[derive(Debug, Clone)]
 pub struct CompressionMetadata {
     pub version: u8,
+    pub codec_id: u8,
+    pub lossless: bool,
+    pub delta_codec: u8,
     pub num_symbols: usize,
     pub num_ticks: usize,
     pub base_timestamp: u64,
     pub symbols: Vec<String>,
     pub compressed_size: usize,
+    pub uncompressed_size: usize,
     pub reference_checksum: u32,
     pub data_checksum: u32,
     pub overall_checksum: u32,
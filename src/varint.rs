@@ -0,0 +1,117 @@
+//! LEB128 variable-length integer codec: each byte carries 7 data bits
+//! with the high bit set as a continuation marker, so small values (most
+//! counts, lengths, and tick-cadence gaps in this format) collapse to a
+//! single byte instead of the fixed 2-4 bytes the format used before.
+
+use std::io;
+
+/// Appends `value`'s VarInt encoding to `out`.
+pub fn encode_u64(value: u64, out: &mut Vec<u8>) {
+    let mut v = value;
+    loop {
+        let mut byte = (v & 0x7F) as u8;
+        v >>= 7;
+        if v != 0 {
+            byte |= 0x80;
+        }
+        out.push(byte);
+        if v == 0 {
+            break;
+        }
+    }
+}
+
+/// Decodes a VarInt from `data` starting at `pos`, returning the value and
+/// the number of bytes consumed. Fails with `UnexpectedEof` if `data` runs
+/// out before a terminating (high-bit-clear) byte is found, which callers
+/// streaming partial input (e.g. [`crate::stream::TickDecoder`]) treat as
+/// "not enough buffered yet" rather than corruption.
+pub fn decode_u64(data: &[u8], pos: usize) -> io::Result<(u64, usize)> {
+    let mut value: u64 = 0;
+    let mut shift: u32 = 0;
+    let mut i = pos;
+
+    loop {
+        if i >= data.len() {
+            return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "VarInt truncated"));
+        }
+        let byte = data[i];
+        i += 1;
+
+        if shift >= 64 {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "VarInt too long"));
+        }
+        value |= ((byte & 0x7F) as u64) << shift;
+
+        if byte & 0x80 == 0 {
+            break;
+        }
+        shift += 7;
+    }
+
+    Ok((value, i - pos))
+}
+
+/// Maps a signed value to an unsigned one so small magnitudes (positive or
+/// negative) both encode to a small VarInt, for values like delta-of-delta
+/// tick gaps that can go either way.
+pub fn zigzag_encode(value: i64) -> u64 {
+    ((value << 1) ^ (value >> 63)) as u64
+}
+
+pub fn zigzag_decode(value: u64) -> i64 {
+    ((value >> 1) as i64) ^ -((value & 1) as i64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_roundtrip_small_values() {
+        for v in [0u64, 1, 127, 128, 300, 16384, u32::MAX as u64] {
+            let mut buf = Vec::new();
+            encode_u64(v, &mut buf);
+            let (decoded, consumed) = decode_u64(&buf, 0).unwrap();
+            assert_eq!(decoded, v);
+            assert_eq!(consumed, buf.len());
+        }
+    }
+
+    #[test]
+    fn test_small_values_use_one_byte() {
+        let mut buf = Vec::new();
+        encode_u64(42, &mut buf);
+        assert_eq!(buf.len(), 1);
+    }
+
+    #[test]
+    fn test_decode_multiple_values_from_one_buffer() {
+        let mut buf = Vec::new();
+        encode_u64(1, &mut buf);
+        encode_u64(300, &mut buf);
+        encode_u64(70000, &mut buf);
+
+        let (a, n1) = decode_u64(&buf, 0).unwrap();
+        let (b, n2) = decode_u64(&buf, n1).unwrap();
+        let (c, _n3) = decode_u64(&buf, n1 + n2).unwrap();
+
+        assert_eq!((a, b, c), (1, 300, 70000));
+    }
+
+    #[test]
+    fn test_truncated_buffer_is_unexpected_eof() {
+        let mut buf = Vec::new();
+        encode_u64(70000, &mut buf);
+        buf.truncate(1);
+        let err = decode_u64(&buf, 0).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+    }
+
+    #[test]
+    fn test_zigzag_roundtrip() {
+        for v in [0i64, 1, -1, 2, -2, 12345, -12345] {
+            assert_eq!(zigzag_decode(zigzag_encode(v)), v);
+        }
+    }
+}
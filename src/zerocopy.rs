@@ -0,0 +1,231 @@
+use std::io;
+use std::mem::size_of;
+
+/// Marker for types any same-sized byte pattern can be reinterpreted as:
+/// alignment 1, no padding, and no bit pattern is invalid. Every type below
+/// is `#[repr(transparent)]` over a `[u8; N]` (or, for [`FrameFlags`],
+/// `#[repr(C)]` over nothing but `u8` fields), so the single `unsafe impl`
+/// at each definition is the only place that safety argument needs to be
+/// made — [`ref_from_prefix`] and [`slice_from_prefix`] below are the only
+/// callers of the actual pointer cast, each with one `unsafe` block instead
+/// of one per wrapper type.
+///
+/// This replaces [`CompressedTimeSeries::deserialize`]'s old scattered
+/// `data[pos..pos+k].try_into().unwrap()` reads, each of which panicked
+/// instead of erroring on a short or malicious blob.
+///
+/// [`CompressedTimeSeries::deserialize`]: crate::compression::CompressedTimeSeries::deserialize
+///
+/// # Safety
+/// Implementors must have alignment 1, no padding, and treat every bit
+/// pattern of the right size as a valid instance.
+unsafe trait AnyBitPattern: Sized {}
+
+/// Validates `data` holds at least `size_of::<T>()` bytes and returns a
+/// reference to its prefix reinterpreted as `T`, plus the remaining bytes.
+fn ref_from_prefix<T: AnyBitPattern>(data: &[u8], err_msg: &'static str) -> io::Result<(&T, &[u8])> {
+    let size = size_of::<T>();
+    if data.len() < size {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, err_msg));
+    }
+    let (head, rest) = data.split_at(size);
+    // SAFETY: `T: AnyBitPattern` guarantees alignment 1, no padding, and
+    // that every bit pattern is valid; `head` was just checked to be
+    // exactly `size_of::<T>()` bytes, matching `T`'s layout exactly.
+    let value = unsafe { &*(head.as_ptr() as *const T) };
+    Ok((value, rest))
+}
+
+/// Validates `data` holds at least `n * size_of::<T>()` bytes and returns a
+/// slice of `n` `T`s reinterpreted from its prefix, plus the remaining
+/// bytes.
+fn slice_from_prefix<T: AnyBitPattern>(
+    data: &[u8],
+    n: usize,
+    err_msg: &'static str,
+) -> io::Result<(&[T], &[u8])> {
+    let size = n * size_of::<T>();
+    if data.len() < size {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, err_msg));
+    }
+    let (head, rest) = data.split_at(size);
+    // SAFETY: `T: AnyBitPattern` guarantees alignment 1, no padding, and
+    // that every bit pattern is valid, so `n` of them back-to-back occupy
+    // exactly `head`'s bytes; `head` was just checked to be `n *
+    // size_of::<T>()` bytes.
+    let values = unsafe { std::slice::from_raw_parts(head.as_ptr() as *const T, n) };
+    Ok((values, rest))
+}
+
+macro_rules! le_scalar {
+    ($(#[$meta:meta])* $name:ident, $prim:ty, $len:expr) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Copy)]
+        #[repr(transparent)]
+        pub struct $name([u8; $len]);
+
+        // SAFETY: `#[repr(transparent)]` over `[u8; $len]` gives alignment
+        // 1 and no padding, and every bit pattern is a valid `[u8; $len]`.
+        unsafe impl AnyBitPattern for $name {}
+
+        impl $name {
+            pub fn get(&self) -> $prim {
+                <$prim>::from_le_bytes(self.0)
+            }
+
+            /// Validates `data` holds at least `size_of::<Self>()` bytes and
+            /// returns a reference to its prefix reinterpreted as `Self`,
+            /// plus the remaining bytes.
+            pub fn ref_from_prefix(data: &[u8]) -> io::Result<(&Self, &[u8])> {
+                ref_from_prefix(data, concat!(stringify!($name), " truncated"))
+            }
+        }
+    };
+}
+
+le_scalar!(
+    /// A little-endian `u32`, e.g. a stored CRC.
+    U32Le,
+    u32,
+    4
+);
+le_scalar!(
+    /// A little-endian `u64`, e.g. the frame's base timestamp.
+    U64Le,
+    u64,
+    8
+);
+
+/// A little-endian `f64`, stored bit-for-bit so its byte layout matches
+/// [`f64::to_le_bytes`] exactly.
+#[derive(Debug, Clone, Copy)]
+#[repr(transparent)]
+pub struct F64Le([u8; 8]);
+
+// SAFETY: `#[repr(transparent)]` over `[u8; 8]` gives alignment 1 and no
+// padding, and every bit pattern is a valid `[u8; 8]`.
+unsafe impl AnyBitPattern for F64Le {}
+
+impl F64Le {
+    pub fn get(&self) -> f64 {
+        f64::from_bits(u64::from_le_bytes(self.0))
+    }
+}
+
+/// A borrowed, bounds-checked view over `n` consecutive little-endian
+/// `f64`s — the reference-frame price vector — read straight out of the
+/// input buffer with a single length check instead of one `try_into`
+/// per price. [`Self::to_vec`] is the one point an owning caller actually
+/// allocates, once it knows it wants a `Vec<f64>` rather than a borrowed
+/// view.
+pub struct F64LeSlice<'a> {
+    values: &'a [F64Le],
+}
+
+impl<'a> F64LeSlice<'a> {
+    pub fn ref_from_prefix(data: &'a [u8], n: usize) -> io::Result<(Self, &'a [u8])> {
+        let (values, rest) = slice_from_prefix(data, n, "Reference frame truncated")?;
+        Ok((F64LeSlice { values }, rest))
+    }
+
+    pub fn to_vec(&self) -> Vec<f64> {
+        self.values.iter().map(F64Le::get).collect()
+    }
+}
+
+/// The frame's four fixed one-byte flags (`version`, `codec_id`,
+/// `lossless`, `delta_codec`), grouped into one `#[repr(C)]` struct so
+/// they're validated and read with a single bounds check instead of four
+/// scattered ones.
+#[derive(Debug, Clone, Copy)]
+#[repr(C)]
+pub struct FrameFlags {
+    pub version: u8,
+    pub codec_id: u8,
+    pub lossless: u8,
+    pub delta_codec: u8,
+}
+
+// SAFETY: `#[repr(C)]` over four `u8` fields only gives alignment 1 and no
+// padding, and every byte pattern is a valid instance.
+unsafe impl AnyBitPattern for FrameFlags {}
+
+impl FrameFlags {
+    pub fn ref_from_prefix(data: &[u8]) -> io::Result<(&Self, &[u8])> {
+        ref_from_prefix(data, "Frame flags truncated")
+    }
+}
+
+/// Splits a `len`-byte prefix off `data`, bounds-checked so a corrupt or
+/// malicious length-prefixed field (symbol table bytes, a compressed
+/// symbol, the codec-compressed payload) never causes an out-of-range
+/// slice panic.
+pub fn bytes_from_prefix(data: &[u8], len: usize) -> io::Result<(&[u8], &[u8])> {
+    if data.len() < len {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "Length-prefixed field truncated",
+        ));
+    }
+    Ok(data.split_at(len))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_u32_le_roundtrip() {
+        let bytes = 0xDEADBEEFu32.to_le_bytes();
+        let (value, rest) = U32Le::ref_from_prefix(&bytes).unwrap();
+        assert_eq!(value.get(), 0xDEADBEEF);
+        assert!(rest.is_empty());
+    }
+
+    #[test]
+    fn test_u64_le_truncated_errors_instead_of_panicking() {
+        let bytes = [0u8; 4];
+        let result = U64Le::ref_from_prefix(&bytes);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_f64_le_slice_roundtrip() {
+        let values = [1.5f64, -2.25, 0.0];
+        let mut bytes = Vec::new();
+        for v in values {
+            bytes.extend_from_slice(&v.to_le_bytes());
+        }
+
+        let (view, rest) = F64LeSlice::ref_from_prefix(&bytes, values.len()).unwrap();
+        assert_eq!(view.to_vec(), values);
+        assert!(rest.is_empty());
+    }
+
+    #[test]
+    fn test_f64_le_slice_truncated_errors() {
+        let bytes = [0u8; 8];
+        let result = F64LeSlice::ref_from_prefix(&bytes, 2);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_frame_flags_roundtrip() {
+        let bytes = [6u8, 1, 0, 1];
+        let (flags, rest) = FrameFlags::ref_from_prefix(&bytes).unwrap();
+        assert_eq!(flags.version, 6);
+        assert_eq!(flags.codec_id, 1);
+        assert_eq!(flags.lossless, 0);
+        assert_eq!(flags.delta_codec, 1);
+        assert!(rest.is_empty());
+    }
+
+    #[test]
+    fn test_bytes_from_prefix_rejects_out_of_range_length() {
+        let bytes = [1u8, 2, 3];
+        assert!(bytes_from_prefix(&bytes, 10).is_err());
+        let (head, rest) = bytes_from_prefix(&bytes, 2).unwrap();
+        assert_eq!(head, &[1, 2]);
+        assert_eq!(rest, &[3]);
+    }
+}